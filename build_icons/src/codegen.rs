@@ -0,0 +1,363 @@
+//! Generation of Rust constants and modules for icon names.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Keywords that need a raw identifier when used as module name.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be used as raw identifiers.
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Replaces all characters that aren't valid in an identifier.
+///
+/// ASCII punctuation and whitespace become `_`, non-ASCII characters
+/// are spelled out as their code point, e.g. `é` becomes `_ue9_`.
+fn sanitize(name: &str, uppercase: bool) -> String {
+    let mut ident = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            ident.push(if uppercase { c.to_ascii_uppercase() } else { c });
+        } else if c.is_ascii() {
+            ident.push('_');
+        } else if uppercase {
+            write!(ident, "_U{:X}_", c as u32).unwrap();
+        } else {
+            write!(ident, "_u{:x}_", c as u32).unwrap();
+        }
+    }
+    ident
+}
+
+/// Whether the sanitized identifier needs a prefix to be valid.
+fn needs_prefix(ident: &str) -> bool {
+    ident.starts_with(|c: char| c.is_ascii_digit()) || ident.chars().all(|c| c == '_')
+}
+
+/// Name of the constant for an icon, such as `EDIT_COPY` for `edit-copy`.
+pub(crate) fn const_name(icon: &str) -> String {
+    let ident = sanitize(icon, true);
+    if needs_prefix(&ident) {
+        format!("ICON_{ident}")
+    } else {
+        ident
+    }
+}
+
+/// Name of the module for a folder of custom icons.
+pub(crate) fn module_name(folder: &str) -> String {
+    let ident = sanitize(folder, false);
+    if needs_prefix(&ident) {
+        format!("icons_{ident}")
+    } else if RESERVED_KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{ident}")
+    } else {
+        ident
+    }
+}
+
+/// Markdown image that renders the icon at `path` inline, for rustdoc and IDE hovers.
+pub(crate) fn preview(icon: &str, path: &Path) -> String {
+    let svg = fs::read(path).unwrap();
+    format!(
+        "![{icon}](data:image/svg+xml;base64,{})",
+        STANDARD.encode(svg)
+    )
+}
+
+/// Value of a generated constant.
+enum Value {
+    /// name of the icon
+    Name,
+    /// string, such as the icon name or resource path
+    Str(String),
+    /// contents of a file, included with `include_bytes!`
+    Bytes(PathBuf),
+}
+
+/// Constant generated for an icon.
+struct Constant {
+    /// documentation of the constant
+    doc: Option<String>,
+    /// name of the icon
+    icon: String,
+    value: Value,
+    /// note of the `#[deprecated]` attribute, if the constant is deprecated
+    deprecated: Option<String>,
+}
+
+/// Generated module with constants and nested modules.
+#[derive(Default)]
+pub(crate) struct Module {
+    /// name of the folder this module was generated from
+    source: String,
+    constants: BTreeMap<String, Constant>,
+    modules: BTreeMap<String, Module>,
+}
+
+impl Module {
+    /// Inserts a constant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another icon maps to the same constant name.
+    fn insert(&mut self, const_name: String, constant: Constant) {
+        if let Some(existing) = self.constants.get(&const_name) {
+            panic!(
+                "Icons `{}` and `{}` both map to the constant `{const_name}`, please rename one of them",
+                existing.icon, constant.icon
+            );
+        }
+        self.constants.insert(const_name, constant);
+    }
+
+    /// Adds a constant for `icon`, named after `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another icon maps to the same constant name.
+    pub(crate) fn add_constant(&mut self, name: &str, icon: &str, doc: Option<String>) {
+        self.insert(
+            const_name(name),
+            Constant {
+                doc,
+                icon: icon.to_owned(),
+                value: Value::Name,
+                deprecated: None,
+            },
+        );
+    }
+
+    /// Adds a deprecated constant named after the old name `name` of `icon`,
+    /// pointing to the constant of the current name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another icon maps to the same constant name.
+    pub(crate) fn add_deprecated_constant(&mut self, name: &str, icon: &str) {
+        self.insert(
+            const_name(name),
+            Constant {
+                doc: Some(format!("Old name of the icon `{icon}`")),
+                icon: name.to_owned(),
+                value: Value::Str(icon.to_owned()),
+                deprecated: Some(format!("use {}", const_name(icon))),
+            },
+        );
+    }
+
+    /// Adds the constants `{NAME}_RESOURCE_PATH` for `icon` and, if `file` is set,
+    /// `{NAME}_BYTES` with the contents of the file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another icon maps to the same constant names.
+    pub(crate) fn add_resource_constants(
+        &mut self,
+        name: &str,
+        icon: &str,
+        resource_path: &str,
+        file: Option<&Path>,
+    ) {
+        let const_name = const_name(name);
+        self.insert(
+            format!("{const_name}_RESOURCE_PATH"),
+            Constant {
+                doc: Some(format!("Resource path of the icon `{icon}`")),
+                icon: icon.to_owned(),
+                value: Value::Str(resource_path.to_owned()),
+                deprecated: None,
+            },
+        );
+        if let Some(file) = file {
+            self.insert(
+                format!("{const_name}_BYTES"),
+                Constant {
+                    doc: Some(format!("SVG file contents of the icon `{icon}`")),
+                    icon: icon.to_owned(),
+                    value: Value::Bytes(file.canonicalize().unwrap()),
+                    deprecated: None,
+                },
+            );
+        }
+    }
+
+    /// Returns the nested module for `folder`, creating it if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another folder maps to the same module name.
+    pub(crate) fn module(&mut self, folder: &str) -> &mut Self {
+        let module = self
+            .modules
+            .entry(module_name(folder))
+            .or_insert_with(|| Self {
+                source: folder.to_owned(),
+                ..Self::default()
+            });
+        if module.source != folder {
+            panic!(
+                "Folders `{}` and `{folder}` both map to the module `{}`, please rename one of them",
+                module.source,
+                module_name(folder)
+            );
+        }
+        module
+    }
+
+    /// Collects the paths of all icon name constants in this module and its nested modules,
+    /// together with the icon name.
+    pub(crate) fn name_constants<'a>(&'a self, path: &str, out: &mut Vec<(String, &'a str)>) {
+        for (const_name, constant) in &self.constants {
+            if matches!(constant.value, Value::Name) {
+                out.push((format!("{path}::{const_name}"), &constant.icon));
+            }
+        }
+        for (module_name, module) in &self.modules {
+            module.name_constants(&format!("{path}::{module_name}"), out);
+        }
+    }
+
+    /// Writes the contents of the module.
+    pub(crate) fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        for (
+            const_name,
+            Constant {
+                doc,
+                icon,
+                value,
+                deprecated,
+            },
+        ) in &self.constants
+        {
+            if let Some(doc) = doc {
+                for line in doc.lines() {
+                    if line.is_empty() {
+                        writeln!(out, "///")?;
+                    } else {
+                        writeln!(out, "/// {line}")?;
+                    }
+                }
+            }
+            if let Some(note) = deprecated {
+                writeln!(out, "#[deprecated(note = {note:?})]")?;
+            }
+            match value {
+                Value::Name => writeln!(out, "pub const {const_name}: &str = {icon:?};")?,
+                Value::Str(value) => writeln!(out, "pub const {const_name}: &str = {value:?};")?,
+                Value::Bytes(path) => {
+                    let path = path.to_str().unwrap();
+                    writeln!(
+                        out,
+                        "pub const {const_name}: &[u8] = include_bytes!({path:?});"
+                    )?;
+                }
+            }
+        }
+        for (module_name, module) in &self.modules {
+            writeln!(out, "pub mod {module_name} {{")?;
+            module.write(out)?;
+            writeln!(out, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn const_names() {
+        assert_eq!(const_name("edit-copy"), "EDIT_COPY");
+        assert_eq!(const_name("x.y z"), "X_Y_Z");
+        assert_eq!(const_name("1.2"), "ICON_1_2");
+        assert_eq!(const_name("2d-1up"), "ICON_2D_1UP");
+        assert_eq!(const_name("--"), "ICON___");
+        assert_eq!(const_name("café"), "CAF_UE9_");
+    }
+
+    #[test]
+    fn module_names() {
+        assert_eq!(module_name("sub-folder"), "sub_folder");
+        assert_eq!(module_name("type"), "r#type");
+        assert_eq!(module_name("match"), "r#match");
+        assert_eq!(module_name("self"), "self_");
+        assert_eq!(module_name("Self"), "Self_");
+        assert_eq!(module_name("crate"), "crate_");
+        assert_eq!(module_name("2d"), "icons_2d");
+        assert_eq!(module_name("_"), "icons__");
+        assert_eq!(module_name("café"), "caf_ue9_");
+    }
+
+    #[test]
+    #[should_panic(expected = "Icons `a-b` and `a_b` both map to the constant `A_B`")]
+    fn constant_collision() {
+        let mut module = Module::default();
+        module.add_constant("a-b", "a-b", None);
+        module.add_constant("a_b", "a_b", None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Folders `a-b` and `a b` both map to the module `a_b`")]
+    fn module_collision() {
+        let mut module = Module::default();
+        module.module("a-b");
+        module.module("a b");
+    }
+
+    #[test]
+    fn nested_module_is_reused() {
+        let mut module = Module::default();
+        module.module("sub").add_constant("x", "x", None);
+        module.module("sub").add_constant("y", "y", None);
+
+        let mut constants = Vec::new();
+        module.name_constants("custom", &mut constants);
+        assert_eq!(
+            constants,
+            [
+                ("custom::sub::X".to_owned(), "x"),
+                ("custom::sub::Y".to_owned(), "y")
+            ]
+        );
+    }
+
+    #[test]
+    fn write_constants() {
+        let mut module = Module::default();
+        module.add_constant("edit-copy", "edit-copy", Some("Copy\n\nMore".into()));
+        module.add_deprecated_constant("copy", "edit-copy");
+        module.module("type").add_constant("x", "x-symbolic", None);
+
+        let mut out = Vec::new();
+        module.write(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "/// Old name of the icon `edit-copy`\n\
+            #[deprecated(note = \"use EDIT_COPY\")]\n\
+            pub const COPY: &str = \"edit-copy\";\n\
+            /// Copy\n\
+            ///\n\
+            /// More\n\
+            pub const EDIT_COPY: &str = \"edit-copy\";\n\
+            pub mod r#type {\n\
+            pub const X: &str = \"x-symbolic\";\n\
+            }\n"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Composition of icons from a base icon and an emblem.
+
+use serde::{Deserialize, Serialize};
+
+use crate::svg;
+
+/// Corner of the base icon an emblem is placed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmblemPosition {
+    /// Top left corner.
+    TopLeft,
+    /// Top right corner.
+    TopRight,
+    /// Bottom left corner.
+    BottomLeft,
+    /// Bottom right corner, the default.
+    #[default]
+    BottomRight,
+    /// Center of the base icon.
+    Center,
+}
+
+/// Icon composed from a base icon and an emblem drawn on top of it,
+/// such as a folder with a lock.
+///
+/// Both icons are looked up like [`Config::icons`](crate::Config::icons),
+/// custom icons take precedence. They aren't bundled themselves unless requested.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct ComposedIcon {
+    /// Name of the base icon.
+    pub base: String,
+    /// Name of the emblem icon.
+    pub emblem: String,
+    /// Corner the emblem is placed in.
+    pub position: EmblemPosition,
+    /// Size of the emblem relative to the base icon.
+    pub scale: f64,
+    /// Gap cut out of the base icon around the emblem, relative to the size of the base icon.
+    ///
+    /// The default is one pixel of a 16 pixel icon.
+    pub margin: f64,
+}
+
+impl Default for ComposedIcon {
+    fn default() -> Self {
+        Self {
+            base: String::new(),
+            emblem: String::new(),
+            position: EmblemPosition::BottomRight,
+            scale: 0.5,
+            margin: 1.0 / 16.0,
+        }
+    }
+}
+
+/// Id of the clip path that cuts the gap around the emblem out of the base icon.
+const CUTOUT_ID: &str = "relm4-icons-emblem-cutout";
+/// Prefix of all ids of the emblem, so they don't collide with the ones of the base icon.
+const EMBLEM_ID_PREFIX: &str = "relm4-icons-emblem-";
+
+impl ComposedIcon {
+    /// Draws the SVG document `emblem` on top of `base`.
+    ///
+    /// Returns `None` if one of the documents has no root element or no known size.
+    pub(crate) fn compose(&self, base: &str, emblem: &str) -> Option<String> {
+        let [x, y, width, height] = svg::view_box(base)?;
+        let [emblem_x, emblem_y, emblem_width, emblem_height] = svg::view_box(emblem)?;
+
+        let scale = (width * self.scale / emblem_width).min(height * self.scale / emblem_height);
+        let (emblem_width, emblem_height) = (emblem_width * scale, emblem_height * scale);
+        let (left, top) = match self.position {
+            EmblemPosition::TopLeft => (x, y),
+            EmblemPosition::TopRight => (x + width - emblem_width, y),
+            EmblemPosition::BottomLeft => (x, y + height - emblem_height),
+            EmblemPosition::BottomRight => (x + width - emblem_width, y + height - emblem_height),
+            EmblemPosition::Center => (
+                x + (width - emblem_width) / 2.0,
+                y + (height - emblem_height) / 2.0,
+            ),
+        };
+        let margin = self.margin * width.max(height);
+
+        let size = |name: &str, default: f64| {
+            svg::root_attribute(base, name).map_or_else(|| default.to_string(), ToOwned::to_owned)
+        };
+        let emblem_contents = svg::contents(emblem)?
+            .replace(" id=\"", &format!(" id=\"{EMBLEM_ID_PREFIX}"))
+            .replace(" id='", &format!(" id='{EMBLEM_ID_PREFIX}"))
+            .replace("url(#", &format!("url(#{EMBLEM_ID_PREFIX}"))
+            .replace("href=\"#", &format!("href=\"#{EMBLEM_ID_PREFIX}"))
+            .replace("href='#", &format!("href='#{EMBLEM_ID_PREFIX}"));
+        Some(format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"{namespaces} width=\"{width_attr}\" height=\"{height_attr}\" viewBox=\"{x} {y} {width} {height}\">\
+            <defs><clipPath id=\"{CUTOUT_ID}\">\
+            <path clip-rule=\"evenodd\" d=\"M{x} {y}h{width}v{height}h{neg_width}z\
+            M{cut_x} {cut_y}h{cut_width}v{cut_height}h{neg_cut_width}z\"/>\
+            </clipPath></defs>\
+            <g clip-path=\"url(#{CUTOUT_ID})\">{base_contents}</g>\
+            <g{emblem_namespaces} transform=\"translate({left} {top}) scale({scale}) translate({neg_emblem_x} {neg_emblem_y})\">{emblem_contents}</g>\
+            </svg>\n",
+            namespaces = svg::namespace_declarations(base),
+            width_attr = size("width", width),
+            height_attr = size("height", height),
+            neg_width = -width,
+            cut_x = left - margin,
+            cut_y = top - margin,
+            cut_width = emblem_width + 2.0 * margin,
+            cut_height = emblem_height + 2.0 * margin,
+            neg_cut_width = -(emblem_width + 2.0 * margin),
+            base_contents = svg::contents(base)?,
+            emblem_namespaces = svg::namespace_declarations(emblem),
+            neg_emblem_x = 0.0 - emblem_x,
+            neg_emblem_y = 0.0 - emblem_y,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16"><path d="M0 0h16v16H0z"/></svg>"#;
+    const EMBLEM: &str = r#"<svg viewBox="0 0 32 32"><circle id="c" fill="url(#g)"/></svg>"#;
+
+    fn composed(position: EmblemPosition) -> ComposedIcon {
+        ComposedIcon {
+            base: "folder".into(),
+            emblem: "lock".into(),
+            position,
+            ..Default::default()
+        }
+    }
+
+    /// Transform of the emblem and cutout of the base icon.
+    fn placement(composed: &ComposedIcon, emblem: &str) -> (String, String) {
+        let svg = composed.compose(BASE, emblem).unwrap();
+        let between = |start: &str, end: &str| {
+            let start = svg.find(start).unwrap() + start.len();
+            svg[start..start + svg[start..].find(end).unwrap()].to_owned()
+        };
+        (between("transform=\"", "\""), between("z", "\""))
+    }
+
+    #[test]
+    fn compose() {
+        assert_eq!(
+            composed(EmblemPosition::BottomRight)
+                .compose(BASE, EMBLEM)
+                .as_deref(),
+            Some(concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 16 16">"#,
+                r#"<defs><clipPath id="relm4-icons-emblem-cutout">"#,
+                r#"<path clip-rule="evenodd" d="M0 0h16v16h-16zM7 7h10v10h-10z"/>"#,
+                r#"</clipPath></defs>"#,
+                r#"<g clip-path="url(#relm4-icons-emblem-cutout)"><path d="M0 0h16v16H0z"/></g>"#,
+                r#"<g transform="translate(8 8) scale(0.25) translate(0 0)">"#,
+                r#"<circle id="relm4-icons-emblem-c" fill="url(#relm4-icons-emblem-g)"/></g>"#,
+                "</svg>\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let placements = [
+            (EmblemPosition::TopLeft, "translate(0 0)", "M-1 -1"),
+            (EmblemPosition::TopRight, "translate(8 0)", "M7 -1"),
+            (EmblemPosition::BottomLeft, "translate(0 8)", "M-1 7"),
+            (EmblemPosition::BottomRight, "translate(8 8)", "M7 7"),
+            (EmblemPosition::Center, "translate(4 4)", "M3 3"),
+        ];
+        for (position, translate, cutout) in placements {
+            assert_eq!(
+                placement(&composed(position), EMBLEM),
+                (
+                    format!("{translate} scale(0.25) translate(0 0)"),
+                    format!("{cutout}h10v10h-10z")
+                ),
+                "{position:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn size() {
+        let composed = ComposedIcon {
+            scale: 0.75,
+            margin: 0.0,
+            ..composed(EmblemPosition::BottomRight)
+        };
+        // The emblem keeps its aspect ratio and is offset by its view box
+        let emblem = r#"<svg viewBox="0 -16 16 8"><path/></svg>"#;
+        assert_eq!(
+            placement(&composed, emblem),
+            (
+                "translate(4 10) scale(0.75) translate(0 16)".to_owned(),
+                "M4 10h12v6h-12z".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn unparsable() {
+        let composed = composed(EmblemPosition::BottomRight);
+        assert_eq!(composed.compose("<path/>", EMBLEM), None);
+        assert_eq!(composed.compose(BASE, "<svg><path/></svg>"), None);
+        assert_eq!(composed.compose(BASE, r#"<svg width="8"><path/>"#), None);
+    }
+}
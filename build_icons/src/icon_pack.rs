@@ -0,0 +1,172 @@
+//! Sources of icons, including icon pack crates.
+//!
+//! An icon pack crate publishes a directory of icons to the build scripts of its dependents.
+//! It sets `links` in its `Cargo.toml` and calls [`IconPack::export`] in its build script,
+//! which passes the directory and license as `DEP_*_RELM4_ICONS_*` variables.
+//! [`bundle_icons_with_config`](crate::bundle_icons_with_config) discovers the packs of all direct
+//! dependencies and the packs forwarded by library crates, and resolves icon names across
+//! the shipped sets and the packs.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{IconSet, output};
+
+/// Suffix of the metadata variable with the directory of an icon pack.
+const DIR_SUFFIX: &str = "_RELM4_ICONS_DIR";
+
+/// Suffix of the metadata variable with the icon packs forwarded by a library.
+const PACKS_SUFFIX: &str = "_RELM4_ICONS_PACKS";
+
+/// A source of named icons, such as a shipped icon set or an icon pack.
+pub(crate) trait IconSource {
+    /// Name of the source, used in the documentation of the generated constants.
+    fn name(&self) -> &str;
+
+    /// SPDX identifier of the license of the icons.
+    fn license(&self) -> &str;
+
+    /// Path of the icon `icon`, if the source contains it.
+    fn icon_path(&self, icon: &str) -> Option<PathBuf>;
+}
+
+impl IconSource for IconSet {
+    fn name(&self) -> &str {
+        self.dir_name()
+    }
+
+    fn license(&self) -> &str {
+        IconSet::license(*self)
+    }
+
+    fn icon_path(&self, icon: &str) -> Option<PathBuf> {
+        IconSet::icon_path(*self, icon)
+    }
+}
+
+/// Directory of icons published by an icon pack crate.
+///
+/// Icons are stored like the shipped icons, as `{icon}-symbolic.svg`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct IconPack {
+    /// Name of the pack.
+    pub name: String,
+    /// Directory with the icons.
+    pub dir: PathBuf,
+    /// SPDX identifier of the license of the icons.
+    pub license: String,
+}
+
+impl IconPack {
+    /// Publishes this pack to the build scripts of dependent crates.
+    ///
+    /// Call this in the build script of an icon pack crate, which must set `links` in its `Cargo.toml`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory doesn't exist or the crate doesn't set `links`.
+    pub fn export(&self) {
+        let dir = self.dir.canonicalize().unwrap_or_else(|_| {
+            panic!(
+                "Icon pack directory `{}` does not exist",
+                self.dir.display()
+            )
+        });
+        output::rerun_if_changed(&dir);
+        output::metadata("relm4_icons_dir", dir.display());
+        output::metadata("relm4_icons_name", &self.name);
+        output::metadata("relm4_icons_license", &self.license);
+    }
+
+    /// Icon packs published by the direct dependencies of the current crate
+    /// or forwarded by library crates, sorted by name.
+    #[must_use]
+    pub fn discover() -> Vec<Self> {
+        let mut packs = Vec::new();
+        for (key, value) in env::vars() {
+            let Some(prefix) = key.strip_prefix("DEP_") else {
+                continue;
+            };
+            if let Some(prefix) = prefix.strip_suffix(DIR_SUFFIX) {
+                let metadata = |name: &str| env::var(format!("DEP_{prefix}_RELM4_ICONS_{name}"));
+                packs.push(Self {
+                    name: metadata("NAME").unwrap_or_else(|_| prefix.to_lowercase()),
+                    dir: PathBuf::from(value),
+                    license: metadata("LICENSE").unwrap_or_default(),
+                });
+            } else if prefix.ends_with(PACKS_SUFFIX) {
+                packs.extend(value.split(';').filter_map(Self::from_forwarded));
+            }
+        }
+        packs.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.dir.cmp(&b.dir)));
+        packs.dedup_by(|a, b| a.dir == b.dir);
+        packs
+    }
+
+    /// Passes the packs to the build scripts of dependent crates, so applications can bundle
+    /// pack icons declared by a library without depending on the packs themselves.
+    pub(crate) fn forward(packs: &[Self]) {
+        let packs = packs
+            .iter()
+            .map(|pack| {
+                let dir = pack.dir.display().to_string();
+                if dir.contains(';') {
+                    panic!(
+                        "Icon pack directory `{dir}` can't be forwarded because it contains `;`"
+                    );
+                }
+                format!("{},{},{dir}", pack.name, pack.license)
+            })
+            .collect::<Vec<_>>();
+        output::metadata("relm4_icons_packs", packs.join(";"));
+    }
+
+    /// Pack forwarded by a library as `{name},{license},{dir}`.
+    fn from_forwarded(pack: &str) -> Option<Self> {
+        let mut fields = pack.splitn(3, ',');
+        Some(Self {
+            name: fields.next()?.to_owned(),
+            license: fields.next()?.to_owned(),
+            dir: PathBuf::from(fields.next()?),
+        })
+    }
+
+    /// Names of all icons in this pack.
+    pub(crate) fn icon_names(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| {
+                let file_name = entry.ok()?.file_name().into_string().ok()?;
+                file_name
+                    .strip_suffix("-symbolic.svg")
+                    .map(ToOwned::to_owned)
+            })
+            .collect()
+    }
+
+    /// Whether the icon at `path` belongs to this pack.
+    pub(crate) fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.dir)
+    }
+}
+
+impl IconSource for IconPack {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn license(&self) -> &str {
+        &self.license
+    }
+
+    fn icon_path(&self, icon: &str) -> Option<PathBuf> {
+        let icon_path = self.dir.join(format!("{icon}-symbolic.svg"));
+        icon_path.exists().then_some(icon_path)
+    }
+}
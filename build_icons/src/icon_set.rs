@@ -0,0 +1,105 @@
+//! Icon sets shipped with `relm4-icons`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{constants, index};
+
+/// An icon set shipped with `relm4-icons`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum IconSet {
+    /// Icons from the GNOME [icon-development-kit](https://gitlab.gnome.org/Teams/Design/icon-development-kit).
+    #[serde(rename = "icon-development-kit")]
+    IconDevelopmentKit,
+    /// Icons from the previous version of the GNOME
+    /// [icon-development-kit](https://gitlab.gnome.org/Teams/Design/icon-development-kit-www).
+    #[serde(rename = "icon-development-kit-www")]
+    IconDevelopmentKitWww,
+    /// Icons from [Fluent UI System Icons](https://github.com/microsoft/fluentui-system-icons).
+    #[serde(rename = "fluentui-system-icons")]
+    FluentSystemIcons,
+    /// Icons from [Material Symbols](https://github.com/marella/material-symbols).
+    #[serde(rename = "material-symbols")]
+    MaterialSymbols,
+}
+
+impl IconSet {
+    /// All shipped icon sets.
+    pub const ALL: [Self; 4] = [
+        Self::IconDevelopmentKit,
+        Self::IconDevelopmentKitWww,
+        Self::FluentSystemIcons,
+        Self::MaterialSymbols,
+    ];
+
+    /// Name of the directory that contains the icons of this set.
+    #[must_use]
+    pub const fn dir_name(self) -> &'static str {
+        match self {
+            Self::IconDevelopmentKit => "icon-development-kit",
+            Self::IconDevelopmentKitWww => "icon-development-kit-www",
+            Self::FluentSystemIcons => "fluentui-system-icons",
+            Self::MaterialSymbols => "material-symbols",
+        }
+    }
+
+    /// SPDX identifier of the license of this set.
+    #[must_use]
+    pub const fn license(self) -> &'static str {
+        match self {
+            Self::IconDevelopmentKit | Self::IconDevelopmentKitWww => "CC0-1.0",
+            Self::FluentSystemIcons => "MIT",
+            Self::MaterialSymbols => "Apache-2.0",
+        }
+    }
+
+    /// Set that contains the shipped icon at `path`.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|set| path.starts_with(set.dir()))
+    }
+
+    /// Directory that contains the icons of this set.
+    #[must_use]
+    pub fn dir(self) -> PathBuf {
+        Path::new(constants::SHIPPED_ICONS_PATH).join(self.dir_name())
+    }
+
+    /// Path of the shipped icon `icon` in this set, if it exists.
+    #[must_use]
+    pub fn icon_path(self, icon: &str) -> Option<PathBuf> {
+        let icon_path = self.dir().join(format!("{icon}-symbolic.svg"));
+        index::contains(&icon_path).then_some(icon_path)
+    }
+
+    /// Names of all icons in this set, without hand-tuned sizes or other styles.
+    #[must_use]
+    pub fn icon_names(self) -> Vec<String> {
+        let mut names = index::files(&self.dir())
+            .filter(|file| !file.contains('/'))
+            .filter_map(|file| file.strip_suffix("-symbolic.svg"))
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+}
+
+/// Names of all icons in all shipped sets.
+pub(crate) fn shipped_icon_names() -> BTreeSet<String> {
+    IconSet::ALL
+        .into_iter()
+        .flat_map(IconSet::icon_names)
+        .collect()
+}
+
+impl fmt::Display for IconSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.dir_name())
+    }
+}
@@ -0,0 +1,164 @@
+//! Precomputed index of the shipped icons, generated by `update_icons`.
+//!
+//! Looking up icons in the index avoids probing and listing the large
+//! folders of shipped icons on every build.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::constants;
+
+/// Paths of all shipped icons relative to the shipped icons folder, sorted.
+///
+/// Each line of the index is `{path} {hash}`, the hash is only used by `update_icons`.
+static FILES: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    include_str!("shipped_index.txt")
+        .lines()
+        .map(|line| line.split_once(' ').map_or(line, |(path, _)| path))
+        .collect()
+});
+
+/// Paths of all folders with shipped icons relative to the shipped icons folder, sorted.
+static DIRS: LazyLock<Vec<&'static str>> = LazyLock::new(|| dirs(&FILES));
+
+/// Folders of `files` and their parent folders, sorted and without duplicates.
+fn dirs(files: &[&'static str]) -> Vec<&'static str> {
+    let dirs = files
+        .iter()
+        .flat_map(|file| file.match_indices('/').map(|(index, _)| &file[..index]))
+        .collect::<BTreeSet<_>>();
+    dirs.into_iter().collect()
+}
+
+/// Path of a shipped file or folder relative to the shipped icons folder, with `/` as separator.
+fn relative_path(path: &Path) -> Option<String> {
+    let relative_path = path.strip_prefix(constants::SHIPPED_ICONS_PATH).ok()?;
+    let components = relative_path
+        .iter()
+        .map(|component| component.to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(components.join("/"))
+}
+
+/// Whether the shipped icon at `path` exists.
+pub(crate) fn contains(path: &Path) -> bool {
+    relative_path(path).is_some_and(|path| FILES.binary_search(&path.as_str()).is_ok())
+}
+
+/// Paths of all shipped icons in `dir` and its subfolders, relative to `dir`.
+pub(crate) fn files(dir: &Path) -> impl Iterator<Item = &'static str> {
+    files_with_prefix(dir, "")
+}
+
+/// Paths of the shipped icons in `dir` and its subfolders starting with `prefix`, relative to `dir`.
+pub(crate) fn files_with_prefix(dir: &Path, prefix: &str) -> impl Iterator<Item = &'static str> {
+    let dir_len = relative_path(dir).map_or(0, |dir| dir.len() + 1);
+    let prefix = relative_path(dir).map(|dir| format!("{dir}/{prefix}"));
+    let files = prefix.map_or(&[][..], |prefix| with_prefix(&FILES, &prefix));
+    files.iter().map(move |file| &file[dir_len..])
+}
+
+/// Names of the direct subfolders of `dir` that contain shipped icons.
+pub(crate) fn subdirs(dir: &Path) -> impl Iterator<Item = &'static str> {
+    let prefix = relative_path(dir).map(|dir| format!("{dir}/"));
+    prefix
+        .into_iter()
+        .flat_map(|prefix| children(&DIRS, &prefix))
+}
+
+/// Direct children of the folder `prefix`, which ends with `/`, in the sorted `dirs`.
+fn children(dirs: &[&'static str], prefix: &str) -> Vec<&'static str> {
+    with_prefix(dirs, prefix)
+        .iter()
+        .map(|dir| &dir[prefix.len()..])
+        .filter(|dir| !dir.contains('/'))
+        .collect()
+}
+
+/// Entries of the sorted `paths` starting with `prefix`.
+fn with_prefix<'a>(paths: &'a [&'static str], prefix: &str) -> &'a [&'static str] {
+    // Paths with the same prefix are next to each other in the sorted index
+    let start = paths.partition_point(|path| *path < prefix);
+    let len = paths[start..].partition_point(|path| path.starts_with(prefix));
+    &paths[start..start + len]
+}
+
+#[cfg(test)]
+mod tests {
+    use walkdir::WalkDir;
+
+    use super::*;
+
+    #[test]
+    fn prefix_boundaries() {
+        let paths = [
+            "foo-bar-symbolic.svg",
+            "foo-bar/a-symbolic.svg",
+            "foo-symbolic.svg",
+            "foo/a-symbolic.svg",
+            "foo/b-symbolic.svg",
+            "foo0/c-symbolic.svg",
+            "fop-symbolic.svg",
+        ];
+        assert_eq!(
+            with_prefix(&paths, "foo/"),
+            ["foo/a-symbolic.svg", "foo/b-symbolic.svg"]
+        );
+        assert_eq!(
+            with_prefix(&paths, "foo-"),
+            [
+                "foo-bar-symbolic.svg",
+                "foo-bar/a-symbolic.svg",
+                "foo-symbolic.svg"
+            ]
+        );
+        assert_eq!(with_prefix(&paths, "foo-bar/"), ["foo-bar/a-symbolic.svg"]);
+        assert!(with_prefix(&paths, "fo/").is_empty());
+        assert!(with_prefix(&paths, "fop/").is_empty());
+    }
+
+    #[test]
+    fn subdirs() {
+        let dirs = dirs(&[
+            "set/a-symbolic.svg",
+            "set/24x24/a-symbolic.svg",
+            "set/24x24/b-symbolic.svg",
+            "set/32x32/a-symbolic.svg",
+            "set/deep/nested/a-symbolic.svg",
+            "set-other/48x48/a-symbolic.svg",
+        ]);
+        assert_eq!(
+            dirs,
+            [
+                "set",
+                "set-other",
+                "set-other/48x48",
+                "set/24x24",
+                "set/32x32",
+                "set/deep",
+                "set/deep/nested"
+            ]
+        );
+        assert_eq!(children(&dirs, "set/"), ["24x24", "32x32", "deep"]);
+        assert_eq!(children(&dirs, "set/deep/"), ["nested"]);
+    }
+
+    #[test]
+    fn index_is_sorted() {
+        assert!(FILES.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn index_matches_icons_folder() {
+        let mut files = WalkDir::new(constants::SHIPPED_ICONS_PATH)
+            .into_iter()
+            .map(Result::unwrap)
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| relative_path(entry.path()))
+            .filter(|path| path.ends_with("-symbolic.svg"))
+            .collect::<Vec<_>>();
+        files.sort_unstable();
+        assert_eq!(files, *FILES, "run `update_icons` to regenerate the index");
+    }
+}
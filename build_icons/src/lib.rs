@@ -1,6 +1,6 @@
 //! Utilities for build scripts using `relm4-icons`.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::env;
 use std::fs;
 use std::fs::File;
@@ -8,24 +8,462 @@ use std::io::BufWriter;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use gvdb::gresource::{BundleBuilder, FileData, PreprocessOptions};
+use gvdb::gresource::{BundleBuilder, FileData};
 use serde::Deserialize;
 use serde::Serialize;
 use walkdir::WalkDir;
 
+use codegen::Module;
+use icon_pack::IconSource;
+use resource::ResourceFile;
+
+mod codegen;
+mod emblem;
+mod icon_pack;
+mod icon_set;
+mod index;
+mod library;
+mod manifest;
+mod material;
+mod output;
+mod parallel;
+pub mod renames;
+mod report;
+mod resource;
+mod resource_xml;
+mod scan;
+pub mod semantic;
+mod svg;
+mod symbolic_png;
+mod theme;
+mod transform;
+mod ui;
+
+pub use emblem::{ComposedIcon, EmblemPosition};
+pub use icon_pack::IconPack;
+pub use icon_set::IconSet;
+pub use library::declare_icons;
+pub use material::{MaterialOptions, MaterialStyle};
+pub use resource::{Preprocess, Resource};
+pub use theme::install_icon_theme;
+pub use transform::TransformedIcon;
+
 /// Stores data for each icon:
 struct IconData {
     /// actual location on disk
     path: PathBuf,
     /// whether the icon is part of the shipped set
     is_shipped: bool,
+    /// shipped set or icon pack the icon is from
+    source: Option<Source>,
+    /// variants bundled next to the icon
+    variants: Vec<IconVariant>,
+    /// shipped icon that is deliberately replaced by this custom icon
+    overrides: Option<String>,
+    /// description of how the icon was generated from other icons at build time
+    derivation: Option<String>,
+}
+
+/// Set or pack a shipped icon is from.
+struct Source {
+    name: String,
+    /// SPDX identifier of the license
+    license: String,
+}
+
+impl Source {
+    /// Source of the icon at `path`.
+    fn of(path: &Path, packs: &[IconPack]) -> Option<Self> {
+        let set = IconSet::from_path(path);
+        let source: &dyn IconSource = match &set {
+            Some(set) => set,
+            None => packs.iter().find(|pack| pack.contains(path))?,
+        };
+        Some(Self {
+            name: source.name().to_owned(),
+            license: source.license().to_owned(),
+        })
+    }
+}
+
+/// Variant of an icon, bundled next to it.
+struct IconVariant {
+    /// tag such as `rtl` or `locale-de`, inserted into the file name
+    tag: Option<String>,
+    /// size of a hand-tuned or pre-rendered version, bundled into the matching hicolor size directory
+    size: Option<u32>,
+    /// whether this is a pre-rendered symbolic PNG instead of an SVG
+    symbolic_png: bool,
+    /// actual location on disk
+    path: PathBuf,
+}
+
+impl IconData {
+    /// File name of the icon inside the resource bundle.
+    ///
+    /// The optional `tag` (such as `locale-de`) is inserted before the `-symbolic` suffix,
+    /// except for `rtl`, which GTK looks up after the full icon name, as in `{icon}-symbolic-rtl`.
+    fn file_name(&self, icon: &str, tag: Option<&str>) -> String {
+        let (name, symbolic) = if self.is_shipped {
+            (icon, "-symbolic")
+        } else if let Some(name) = icon.strip_suffix("-symbolic") {
+            (name, "-symbolic")
+        } else {
+            (icon, "")
+        };
+        match tag {
+            Some("rtl") => format!("{name}{symbolic}-rtl.svg"),
+            Some(tag) => format!("{name}-{tag}{symbolic}.svg"),
+            None => format!("{name}{symbolic}.svg"),
+        }
+    }
+
+    /// Files of the icon and all its variants, with their path inside the resource bundle
+    /// relative to the resource prefix.
+    fn resource_files<'a>(&'a self, icon: &str) -> Vec<(String, &'a Path)> {
+        let variant_files = self.variants.iter().map(|variant| {
+            let dir = match variant.size {
+                Some(size) => format!("{size}x{size}"),
+                None => "scalable".into(),
+            };
+            let mut file_name = self.file_name(icon, variant.tag.as_deref());
+            if variant.symbolic_png {
+                file_name = file_name.replace(".svg", ".symbolic.png");
+            }
+            (format!("{dir}/actions/{file_name}"), variant.path.as_path())
+        });
+        std::iter::once((
+            format!("scalable/actions/{}", self.file_name(icon, None)),
+            self.path.as_path(),
+        ))
+        .chain(variant_files)
+        .collect()
+    }
+}
+
+/// Variants of a shipped icon.
+///
+/// Tagged variants are stored next to the icon as `{icon}-rtl-symbolic.svg`
+/// or `{icon}-locale-{tag}-symbolic.svg`, hand-tuned sizes in subdirectories
+/// such as `24x24`.
+fn shipped_variants(icon_path: &Path, icon: &str) -> Vec<IconVariant> {
+    let dir = icon_path.parent().unwrap();
+    let prefix = format!("{icon}-");
+    // Tag of a variant file name, `None` for the untagged icon
+    let tag = |file_name: &str| -> Option<Option<String>> {
+        let rest = file_name.strip_suffix("-symbolic.svg")?;
+        if rest == icon {
+            return Some(None);
+        }
+        let tag = rest.strip_prefix(&prefix)?;
+        (tag == "rtl" || tag.starts_with("locale-")).then(|| Some(tag.to_owned()))
+    };
+
+    let mut variants = Vec::new();
+    // Only look up the files starting with the icon name in the sorted index
+    for file in index::files_with_prefix(dir, &prefix) {
+        if let Some(Some(tag)) = tag(file).filter(|_| !file.contains('/')) {
+            variants.push(IconVariant {
+                tag: Some(tag),
+                size: None,
+                symbolic_png: false,
+                path: dir.join(file),
+            });
+        }
+    }
+    for size_dir in index::subdirs(dir) {
+        let Some(size) = size_dir
+            .split_once('x')
+            .filter(|(width, height)| width == height)
+            .and_then(|(width, _)| width.parse().ok())
+        else {
+            continue;
+        };
+        // For size folders, the untagged icon is a variant as well
+        for file in index::files_with_prefix(dir, &format!("{size_dir}/{icon}")) {
+            let file_name = &file[size_dir.len() + 1..];
+            if let Some(tag) = tag(file_name).filter(|_| !file_name.contains('/')) {
+                variants.push(IconVariant {
+                    tag,
+                    size: Some(size),
+                    symbolic_png: false,
+                    path: dir.join(file),
+                });
+            }
+        }
+    }
+
+    variants.sort_by(|a, b| (a.size, &a.tag).cmp(&(b.size, &b.tag)));
+    variants
+}
+
+/// Checks whether a custom icon replaces the shipped icon `icon`.
+///
+/// Returns `true` if the shipped icon is overridden and must not be bundled.
+/// Panics if the collision isn't listed in [`Config::overrides`].
+fn override_shipped_icon(
+    icons: &mut HashMap<String, IconData>,
+    config: &Config,
+    icon: &str,
+) -> bool {
+    let custom_icon = [icon.to_owned(), format!("{icon}-symbolic")]
+        .into_iter()
+        .find(|name| icons.get(name).is_some_and(|data| !data.is_shipped));
+    let Some(custom_icon) = custom_icon else {
+        return false;
+    };
+    if !config.overrides.iter().any(|name| name == icon) {
+        panic!(
+            "Icon with name `{icon}` exists twice, add it to the overrides to replace the shipped icon"
+        );
+    }
+
+    let data = icons.get_mut(&custom_icon).unwrap();
+    if data.overrides.is_none() {
+        output::warning(format!(
+            "Custom icon `{custom_icon}` overrides shipped icon `{icon}`"
+        ));
+        data.overrides = Some(icon.to_owned());
+    }
+    true
+}
+
+/// Finds the file of an icon another icon is derived from and whether it is symbolic.
+///
+/// Custom and derived icons take precedence, `shipped_path` looks up shipped icons.
+fn derivation_input(
+    icons: &HashMap<String, IconData>,
+    icon: &str,
+    shipped_path: impl FnOnce() -> Option<PathBuf>,
+) -> Option<(PathBuf, bool)> {
+    [format!("{icon}-symbolic"), icon.to_owned()]
+        .into_iter()
+        .find_map(|key| {
+            let data = icons.get(&key).filter(|data| !data.is_shipped)?;
+            Some((data.path.clone(), key.ends_with("-symbolic")))
+        })
+        .or_else(|| shipped_path().map(|path| (path, true)))
+}
+
+/// Writes the icon `name` derived from other icons to `out_dir` and adds it to the icons.
+///
+/// The icon is bundled with a `-symbolic` suffix if `symbolic` is set.
+fn add_derived_icon(
+    icons: &mut HashMap<String, IconData>,
+    out_dir: &Path,
+    name: &str,
+    symbolic: bool,
+    svg: String,
+    derivation: String,
+) {
+    let name = name.trim_end_matches("-symbolic");
+    let key = if symbolic {
+        format!("{name}-symbolic")
+    } else {
+        name.to_owned()
+    };
+    if icons.contains_key(name) || icons.contains_key(&format!("{name}-symbolic")) {
+        panic!("Icon with name `{name}` exists twice");
+    }
+    let derived_dir = out_dir.join("derived-icons");
+    fs::create_dir_all(&derived_dir).unwrap();
+    let path = derived_dir.join(format!("{key}.svg"));
+    fs::write(&path, svg).unwrap();
+    let data = IconData {
+        path,
+        is_shipped: false,
+        source: None,
+        variants: Vec::new(),
+        overrides: None,
+        derivation: Some(derivation),
+    };
+    icons.insert(key, data);
 }
 
-#[derive(Serialize, Deserialize)]
-struct Config {}
+/// Configuration for [`bundle_icons_with_config`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// Optional app ID, used to derive the resource path.
+    pub app_id: Option<String>,
+    /// Custom base resource path.
+    ///
+    /// Defaults to the path derived from the app ID or `/org/relm4` otherwise.
+    pub base_resource_path: Option<String>,
+    /// Directory with custom icons (if any).
+    pub icons_folder: Option<PathBuf>,
+    /// Names of shipped icons to include.
+    ///
+    /// Icons are looked up in the shipped sets first and in the icon packs afterwards.
+    /// Icons declared by dependencies with [`declare_icons`] are included automatically.
+    pub icons: Vec<String>,
+    /// Icon packs in addition to the ones published by dependencies, see [`IconPack`].
+    pub icon_packs: Vec<IconPack>,
+    /// Folders with Rust sources that are scanned for used shipped icons.
+    ///
+    /// If set, exactly the shipped icons referenced as `icon_names::*` constants or passed as
+    /// string literals to `set_icon_name`/`icon_name` are bundled instead of [`Config::icons`].
+    /// Icons listed in [`Config::icons`] that aren't used are reported with a build warning.
+    pub source_folders: Vec<PathBuf>,
+    /// Folders with GtkBuilder `.ui` and Blueprint `.blp` files whose icon names are validated.
+    ///
+    /// Every `icon-name` must be bundled, a shipped icon, an icon of a pack or listed in
+    /// [`Config::allowed_ui_icons`], otherwise the build fails.
+    /// Shipped and pack icons that aren't bundled are reported with a build warning.
+    pub ui_folders: Vec<PathBuf>,
+    /// Icon names in [`Config::ui_folders`] that aren't validated, such as icons of the system theme
+    /// or the app icon.
+    ///
+    /// A trailing `*` matches all names with the prefix, such as `application-x-*`.
+    pub allowed_ui_icons: Vec<String>,
+    /// Whether to bundle the shipped icons referenced in [`Config::ui_folders`] automatically.
+    pub bundle_ui_icons: bool,
+    /// Semantic icons to include, see [`semantic::CATALOG`].
+    ///
+    /// Constants for them are generated in the `semantic` module.
+    pub semantic_icons: Vec<String>,
+    /// Icon set semantic icons are resolved against.
+    ///
+    /// Concepts missing in this set fall back to the other sets.
+    pub preferred_set: Option<IconSet>,
+    /// Custom icons that depend on the text direction.
+    ///
+    /// A horizontally mirrored variant is generated and bundled as `{icon}-rtl`
+    /// or `{icon}-symbolic-rtl`, which GTK uses for right-to-left locales.
+    pub directional_icons: Vec<String>,
+    /// Style, weight and fill of all shipped icons from `material-symbols`.
+    pub material_options: Option<MaterialOptions>,
+    /// Style, weight and fill of individual icons from `material-symbols`.
+    ///
+    /// Takes precedence over [`Config::material_options`].
+    /// The icon keeps its name, regardless of the selected file.
+    pub material_icons: BTreeMap<String, MaterialOptions>,
+    /// Shipped icons that are deliberately replaced by a custom icon of the same name.
+    ///
+    /// The custom icon may also carry a `-symbolic` suffix. Without this, custom
+    /// icons that collide with a requested shipped icon cause a build failure.
+    pub overrides: Vec<String>,
+    /// Whether to generate `{NAME}_BYTES` constants with the SVG file of each icon.
+    ///
+    /// `{NAME}_RESOURCE_PATH` constants with the full resource path are always generated.
+    pub include_bytes: bool,
+    /// Maximum size of the generated `.gresource` file in bytes.
+    ///
+    /// The build fails if the bundle is larger.
+    pub size_budget: Option<u64>,
+    /// Directory to export the bundled icons to as a freedesktop icon theme.
+    ///
+    /// The icons are written with the same names and contexts as in the resource bundle,
+    /// together with an `index.theme`, so the tree can be installed into
+    /// `share/icons/hicolor` with [`install_icon_theme`]. Stale icons aren't removed.
+    pub icon_theme_dir: Option<PathBuf>,
+    /// Path to write a `.gresource.xml` manifest to, for `glib-compile-resources`.
+    ///
+    /// The manifest references the icon files by their absolute path and produces
+    /// the same resources as the generated bundle.
+    pub gresource_xml: Option<PathBuf>,
+    /// Additional files or directories bundled together with the icons, such as CSS or `.ui` files.
+    pub resources: Vec<Resource>,
+    /// Sizes to pre-render symbolic icons at, as symbolic PNGs.
+    ///
+    /// The PNGs are encoded like `gtk4-encode-symbolic-svg` does and bundled as
+    /// `{size}x{size}/actions/{icon}-symbolic.symbolic.png`, so GTK can recolor them
+    /// at these sizes without loading SVGs.
+    pub symbolic_png_sizes: Vec<u32>,
+    /// Icons composed from a base icon and an emblem, by name.
+    ///
+    /// Constants for them are generated in the `derived` module. The composed icon
+    /// is symbolic if both icons are, a `-symbolic` suffix of the name is optional.
+    pub composed_icons: BTreeMap<String, ComposedIcon>,
+    /// Icons transformed from another icon, such as a mirrored or rotated arrow, by name.
+    ///
+    /// Constants for them are generated in the `derived` module. Transformed icons
+    /// can be used as base or emblem of [`Config::composed_icons`].
+    pub transformed_icons: BTreeMap<String, TransformedIcon>,
+}
+
+impl Config {
+    /// Reads a config from a TOML file with kebab-case keys, such as `icons-folder`.
+    ///
+    /// Relative input paths are resolved against the directory of the file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file can't be read or parsed.
+    pub fn from_file(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("Couldn't read config file `{}`: {err}", path.display()));
+        let mut config: Self = toml::from_str(&contents)
+            .unwrap_or_else(|err| panic!("Couldn't parse config file `{}`: {err}", path.display()));
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        let resolve = |path: &mut PathBuf| *path = base.join(&*path);
+        config.icons_folder.iter_mut().for_each(resolve);
+        config.source_folders.iter_mut().for_each(resolve);
+        config.ui_folders.iter_mut().for_each(resolve);
+        config
+            .resources
+            .iter_mut()
+            .for_each(|resource| resolve(&mut resource.path));
+        config
+            .icon_packs
+            .iter_mut()
+            .for_each(|pack| resolve(&mut pack.dir));
+        config
+    }
+
+    /// Whether `icon` is listed in [`Config::allowed_ui_icons`].
+    fn is_allowed_ui_icon(&self, icon: &str) -> bool {
+        self.allowed_ui_icons
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => icon.starts_with(prefix),
+                None => icon == pattern,
+            })
+    }
+
+    /// Resolves the Material Symbols options of a shipped icon.
+    ///
+    /// `icon_path` is the default location of the icon. Per-icon options select
+    /// Material Symbols even if another set has an icon with the same name.
+    /// Styles and weights that aren't shipped are looked up in the icon `packs`.
+    fn resolve_material_icon(
+        &self,
+        icon: &str,
+        icon_path: Option<PathBuf>,
+        packs: &[IconPack],
+    ) -> Option<PathBuf> {
+        let options = if let Some(options) = self.material_icons.get(icon) {
+            options
+        } else {
+            let is_material = icon_path.as_ref().and_then(|path| path.parent())
+                == Some(IconSet::MaterialSymbols.dir().as_path());
+            match &self.material_options {
+                Some(options) if is_material => options,
+                _ => return icon_path,
+            }
+        };
+        if let Some(icon_path) = options.icon_path(icon, packs) {
+            return Some(icon_path);
+        }
+        if options.is_shipped() {
+            panic!("Icon with name `{icon}` does not exist in material-symbols as {options}");
+        }
+        panic!(
+            "Icon with name `{icon}` does not exist in material-symbols as {options}. \
+            Only outlined 400 is shipped, add an icon pack with a `{}/{}` folder for other styles and weights",
+            options.style.name(),
+            options.weight
+        );
+    }
+}
 
 /// Constants file with paths to icons.
 pub mod constants {
+    /// File in `OUT_DIR` with the names of all bundled icons, read by `relm4_icons::icon!`.
+    pub const NAMES_MANIFEST: &str = "relm4-icons-names.txt";
+
     pub const SHIPPED_ICONS_PATH: &str =
         include_str!(concat!(env!("OUT_DIR"), "/shipped_icons.txt"));
 }
@@ -34,17 +472,10 @@ const GENERAL_PREFIX: &str = "/org/relm4/icons";
 
 /// Parse a filename into icon name.
 /// - Strips `.svg`
+/// - Returns `None` for other files and folders, which are ignored
 pub fn path_to_icon_alias(path: impl AsRef<Path>) -> Option<String> {
     match path.as_ref().to_str() {
-        Some(path) => {
-            if path.ends_with(".svg") {
-                println!("{path}");
-                Some(path.trim_end_matches(".svg").to_owned())
-            } else {
-                println!("Found non-icon file `{path}`, ignoring");
-                None
-            }
-        }
+        Some(path) => path.strip_suffix(".svg").map(ToOwned::to_owned),
         None => panic!(
             "Failed to convert file path `{:?}` to string",
             path.as_ref()
@@ -67,63 +498,165 @@ pub fn bundle_icons<P, I, S>(
     I: IntoIterator<Item = S>,
     S: AsRef<str>,
 {
+    let config = Config {
+        app_id: app_id.map(Into::into),
+        base_resource_path: base_resource_path.map(Into::into),
+        icons_folder: icons_folder.map(|folder| folder.as_ref().to_path_buf()),
+        icons: icon_names
+            .into_iter()
+            .map(|icon| icon.as_ref().to_owned())
+            .collect(),
+        ..Config::default()
+    };
+    bundle_icons_with_config(out_file_name, &config);
+}
+
+/// Bundles icons into a `.gresource` file and generates Rust constants for icon names.
+///
+/// Same as [`bundle_icons`], but takes all options from a [`Config`].
+pub fn bundle_icons_with_config(out_file_name: &str, config: &Config) {
     let out_dir = env::var("OUT_DIR").unwrap();
-    let out_dir = Path::new(&out_dir);
+    output::set_build_script(true);
+    bundle(Path::new(&out_dir), out_file_name, config);
+}
+
+/// Bundles icons into `out_dir` outside of a Cargo build script, for example for Meson.
+///
+/// Writes the same files as [`bundle_icons_with_config`] and reports warnings on stderr
+/// instead of printing instructions for Cargo.
+pub fn bundle_icons_to_dir(out_dir: impl AsRef<Path>, out_file_name: &str, config: &Config) {
+    let out_dir = out_dir.as_ref();
+    fs::create_dir_all(out_dir).unwrap();
+    output::set_build_script(false);
+    bundle(out_dir, out_file_name, config);
+}
+
+/// Bundles icons into `out_dir`.
+fn bundle(out_dir: &Path, out_file_name: &str, config: &Config) {
+    let app_id = config.app_id.as_deref();
+    let base_resource_path = config.base_resource_path.as_deref();
+    let icons_folder = config.icons_folder.as_ref();
     let mut icons: HashMap<String, IconData> = HashMap::new();
 
     // Package custom icons
     if let Some(folder) = &icons_folder {
-        println!("cargo:rerun-if-changed={}", folder.as_ref().display());
+        output::rerun_if_changed(folder);
 
         let read_dir = WalkDir::new(folder);
         for entry in read_dir {
             let entry = entry
                 .expect("Couldn't open icon path specified in config (relative to the manifest)");
-            if let Some(icon) = path_to_icon_alias(entry.path()) {
-                if icons
+            let relative_path = entry.path().strip_prefix(folder).unwrap();
+            if let Some(icon) = path_to_icon_alias(relative_path)
+                && icons
                     .insert(
-                        icon.replace('/', "-").replace('\\', "-").clone(),
+                        icon.replace(['/', '\\'], "-"),
                         IconData {
                             path: entry.path().to_path_buf(),
                             is_shipped: false,
+                            source: None,
+                            variants: Vec::new(),
+                            overrides: None,
+                            derivation: None,
                         },
                     )
                     .is_some()
-                {
-                    panic!("Icon with name `{icon}` exists twice");
-                }
+            {
+                panic!("Icon with name `{icon}` exists twice");
             }
         }
     }
 
-    let shipped_icons_folder = constants::SHIPPED_ICONS_PATH;
+    let mut icon_packs = config.icon_packs.clone();
+    icon_packs.extend(IconPack::discover());
 
-    let dirs = fs::read_dir(shipped_icons_folder)
-        .expect("Couldn't open folder of shipped icons")
-        .map(|entry| {
-            entry
-                .expect("Couldn't open directories in shipped icon folder")
-                .path()
-        })
-        .collect::<Vec<_>>();
+    let ui_references = ui::icon_references(&config.ui_folders);
+    // Icons that can be bundled, from the shipped sets and all icon packs
+    let known_icon_names = if ui_references.is_empty() && config.source_folders.is_empty() {
+        BTreeSet::new()
+    } else {
+        let mut names = icon_set::shipped_icon_names();
+        names.extend(icon_packs.iter().flat_map(IconPack::icon_names));
+        names
+    };
 
-    for icon in icon_names {
-        let icon = icon.as_ref();
-        let icon_path = dirs
-            .iter()
-            .find_map(|dir| {
-                let icon_file_name = format!("{icon}-symbolic.svg");
-                let icon_path = dir.join(icon_file_name);
-                icon_path.exists().then_some(icon_path)
-            })
+    let mut requested_icons: Vec<String> = if config.source_folders.is_empty() {
+        config.icons.clone()
+    } else {
+        let used_icons = scan::used_icons(&config.source_folders, &known_icon_names);
+        for icon in &config.icons {
+            if !used_icons.contains(icon) {
+                output::warning(format!(
+                    "Icon `{icon}` is listed but not used, it is not bundled"
+                ));
+            }
+        }
+        used_icons.into_iter().collect()
+    };
+    for icon in library::required_icons() {
+        if !requested_icons.contains(&icon) {
+            requested_icons.push(icon);
+        }
+    }
+
+    if config.bundle_ui_icons {
+        for reference in &ui_references {
+            let icon = reference.icon.trim_end_matches("-symbolic");
+            if known_icon_names.contains(icon)
+                && !icons.contains_key(icon)
+                && !icons.contains_key(&format!("{icon}-symbolic"))
+                && !requested_icons.iter().any(|name| name == icon)
+            {
+                requested_icons.push(icon.to_owned());
+            }
+        }
+    }
+
+    let find_icon = |icon: &str| {
+        IconSet::ALL
+            .into_iter()
+            .find_map(|set| set.icon_path(icon))
+            .or_else(|| icon_packs.iter().find_map(|pack| pack.icon_path(icon)))
+    };
+
+    let mut renamed_icons = BTreeMap::new();
+    for icon in &requested_icons {
+        let mut icon_path = find_icon(icon);
+        let mut icon = icon.as_str();
+        if icon_path.is_none()
+            && !config.material_icons.contains_key(icon)
+            && let Some(rename) = renames::find(icon)
+        {
+            output::warning(format!(
+                "Icon `{icon}` was renamed to `{}` in relm4-icons {}, please use the new name",
+                rename.new, rename.since
+            ));
+            let is_requested = requested_icons.iter().any(|name| name == rename.new)
+                || renamed_icons.values().any(|new| *new == rename.new);
+            renamed_icons.insert(icon, rename.new);
+            if is_requested {
+                continue;
+            }
+            icon = rename.new;
+            icon_path = find_icon(icon);
+        }
+        let icon_path = config
+            .resolve_material_icon(icon, icon_path, &icon_packs)
             .unwrap_or_else(|| panic!("Icon with name `{icon}` does not exist"));
+        if override_shipped_icon(&mut icons, config, icon) {
+            continue;
+        }
 
         if icons
             .insert(
                 icon.to_string(),
                 IconData {
+                    variants: shipped_variants(&icon_path, icon),
+                    source: Source::of(&icon_path, &icon_packs),
                     path: icon_path,
                     is_shipped: true,
+                    overrides: None,
+                    derivation: None,
                 },
             )
             .is_some()
@@ -132,6 +665,208 @@ pub fn bundle_icons<P, I, S>(
         }
     }
 
+    // Resolve semantic icons against the preferred set
+    let preferred_set = config.preferred_set.unwrap_or(IconSet::IconDevelopmentKit);
+    let mut semantic_icons = BTreeMap::new();
+    for name in &config.semantic_icons {
+        let semantic_icon = semantic::find(name)
+            .unwrap_or_else(|| panic!("Semantic icon with name `{name}` does not exist"));
+        let (set, icon) = semantic_icon.resolve(preferred_set);
+
+        let path = config
+            .resolve_material_icon(icon, set.icon_path(icon), &icon_packs)
+            .unwrap_or_else(|| panic!("Icon with name `{icon}` does not exist"));
+        semantic_icons.insert(semantic_icon.name, (set, icon));
+        if override_shipped_icon(&mut icons, config, icon) {
+            continue;
+        }
+        if let Some(existing) = icons.get(icon) {
+            if existing.path != path {
+                panic!(
+                    "Icon with name `{icon}` exists twice: semantic icon `{name}` resolves to it from `{set}`, but it is already bundled from {}",
+                    existing
+                        .source
+                        .as_ref()
+                        .map_or("another set".to_owned(), |source| format!(
+                            "`{}`",
+                            source.name
+                        ))
+                );
+            }
+            continue;
+        }
+        icons.insert(
+            icon.to_owned(),
+            IconData {
+                variants: shipped_variants(&path, icon),
+                source: Source::of(&path, &icon_packs),
+                path,
+                is_shipped: true,
+                overrides: None,
+                derivation: None,
+            },
+        );
+    }
+
+    for name in &config.overrides {
+        if !icons
+            .values()
+            .any(|data| data.overrides.as_ref() == Some(name))
+        {
+            panic!("Override `{name}` doesn't match a requested shipped icon and a custom icon");
+        }
+    }
+
+    // Apply transforms to icons, before they can be used in compositions
+    for (name, transformed) in &config.transformed_icons {
+        let icon = &transformed.icon;
+        let (path, symbolic) = derivation_input(&icons, icon, || {
+            config.resolve_material_icon(icon, find_icon(icon), &icon_packs)
+        })
+        .unwrap_or_else(|| {
+            panic!("Icon `{icon}` used by transformed icon `{name}` does not exist")
+        });
+        if !transformed.rotate.is_multiple_of(90) {
+            panic!("Rotation of transformed icon `{name}` must be a multiple of 90 degrees");
+        }
+        if !(transformed.scale.is_finite() && transformed.scale > 0.0) {
+            panic!("Scale of transformed icon `{name}` must be positive");
+        }
+        if !(0.0..0.5).contains(&transformed.padding) {
+            panic!("Padding of transformed icon `{name}` must be at least 0 and less than 0.5");
+        }
+        let svg = transformed
+            .transform(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|| {
+                panic!("Couldn't transform icon `{name}`: missing size information")
+            });
+        let derivation = format!("`{icon}` {transformed}");
+        add_derived_icon(&mut icons, out_dir, name, symbolic, svg, derivation);
+    }
+
+    // Compose icons from a base icon and an emblem
+    for (name, composed) in &config.composed_icons {
+        let input = |icon: &str| {
+            derivation_input(&icons, icon, || {
+                config.resolve_material_icon(icon, find_icon(icon), &icon_packs)
+            })
+            .unwrap_or_else(|| {
+                panic!("Icon `{icon}` used by composed icon `{name}` does not exist")
+            })
+        };
+        let (base_path, base_symbolic) = input(&composed.base);
+        let (emblem_path, emblem_symbolic) = input(&composed.emblem);
+        let svg = composed
+            .compose(
+                &fs::read_to_string(&base_path).unwrap(),
+                &fs::read_to_string(&emblem_path).unwrap(),
+            )
+            .unwrap_or_else(|| panic!("Couldn't compose icon `{name}`: missing size information"));
+        let derivation = format!("`{}` with the emblem `{}`", composed.base, composed.emblem);
+        let symbolic = base_symbolic && emblem_symbolic;
+        add_derived_icon(&mut icons, out_dir, name, symbolic, svg, derivation);
+    }
+
+    // Generate mirrored variants of directional custom icons
+    for icon in &config.directional_icons {
+        let data = icons
+            .get_mut(icon.as_str())
+            .filter(|data| !data.is_shipped)
+            .unwrap_or_else(|| panic!("Directional icon `{icon}` is not a custom icon"));
+        let svg = fs::read_to_string(&data.path).unwrap();
+        let mirrored = svg::mirror_horizontally(&svg)
+            .unwrap_or_else(|| panic!("Couldn't mirror icon `{icon}`: missing size information"));
+
+        let rtl_dir = out_dir.join("rtl-icons");
+        fs::create_dir_all(&rtl_dir).unwrap();
+        let rtl_path = rtl_dir.join(data.file_name(icon, Some("rtl")));
+        fs::write(&rtl_path, mirrored).unwrap();
+        data.variants.push(IconVariant {
+            tag: Some("rtl".into()),
+            size: None,
+            symbolic_png: false,
+            path: rtl_path,
+        });
+    }
+
+    // Pre-render symbolic icons as symbolic PNGs
+    for size in &config.symbolic_png_sizes {
+        let png_dir = out_dir.join("symbolic-png").join(format!("{size}x{size}"));
+        fs::create_dir_all(&png_dir).unwrap();
+        for (icon, data) in &mut icons {
+            let scalable_tags = std::iter::once(None).chain(
+                data.variants
+                    .iter()
+                    .filter(|variant| variant.size.is_none())
+                    .map(|variant| variant.tag.clone()),
+            );
+            if !data.file_name(icon, None).ends_with("-symbolic.svg") {
+                continue;
+            }
+            let mut pngs = Vec::new();
+            for tag in scalable_tags.collect::<Vec<_>>() {
+                let file_name = data.file_name(icon, tag.as_deref());
+                // Prefer a hand-tuned version of the same size
+                let variant_path = |size: Option<u32>| {
+                    data.variants
+                        .iter()
+                        .find(|variant| {
+                            variant.size == size && variant.tag == tag && !variant.symbolic_png
+                        })
+                        .map(|variant| &variant.path)
+                };
+                let svg_path = variant_path(Some(*size))
+                    .or_else(|| variant_path(None))
+                    .unwrap_or(&data.path);
+                let png = symbolic_png::encode(&fs::read(svg_path).unwrap(), *size)
+                    .unwrap_or_else(|| panic!("Couldn't render icon `{icon}` as symbolic PNG"));
+                let png_path = png_dir.join(file_name.replace(".svg", ".symbolic.png"));
+                fs::write(&png_path, png).unwrap();
+                pngs.push(IconVariant {
+                    tag,
+                    size: Some(*size),
+                    symbolic_png: true,
+                    path: png_path,
+                });
+            }
+            data.variants.extend(pngs);
+        }
+    }
+
+    // Icon names that can be used with GTK, with and without `-symbolic` suffix
+    let mut bundled_names = BTreeSet::new();
+    for (icon, data) in &icons {
+        let file_name = data.file_name(icon, None);
+        let name = file_name.trim_end_matches(".svg");
+        bundled_names.insert(name.trim_end_matches("-symbolic").to_owned());
+        bundled_names.insert(name.to_owned());
+    }
+    let names_manifest = bundled_names
+        .iter()
+        .map(|name| format!("{name}\n"))
+        .collect::<String>();
+    fs::write(out_dir.join(constants::NAMES_MANIFEST), names_manifest).unwrap();
+
+    // Validate icon names used in UI files
+    if !ui_references.is_empty() {
+        for reference in &ui_references {
+            let icon = &reference.icon;
+            if bundled_names.contains(icon) || config.is_allowed_ui_icon(icon) {
+                continue;
+            }
+            if known_icon_names.contains(icon.trim_end_matches("-symbolic")) {
+                output::warning(format!(
+                    "Icon `{icon}` used in `{reference}` is not bundled, add it to the icons or enable `bundle_ui_icons`"
+                ));
+            } else {
+                panic!(
+                    "Icon `{icon}` used in `{reference}` is neither bundled nor a shipped icon, \
+                    add it to `allowed_ui_icons` if it comes from the system theme"
+                );
+            }
+        }
+    }
+
     let prefix = if let Some(base_resource_path) = &base_resource_path {
         format!("{base_resource_path}/icons")
     } else if let Some(app_id) = app_id {
@@ -141,36 +876,178 @@ pub fn bundle_icons<P, I, S>(
     };
     let gresource_file_name = format!("{out_file_name}.gresource");
 
+    if let Some(theme_dir) = &config.icon_theme_dir {
+        theme::export(
+            theme_dir,
+            icons
+                .iter()
+                .flat_map(|(icon, data)| data.resource_files(icon)),
+        );
+    }
+
+    // Collect icons and additional resources
+    let mut resource_files = icons
+        .iter()
+        .flat_map(|(icon, data)| data.resource_files(icon))
+        .map(|(file, path)| ResourceFile::icon(format!("{prefix}/{file}"), path))
+        .collect::<Vec<_>>();
+    let resources_base_path = prefix.strip_suffix("/icons").unwrap();
+    for resource in &config.resources {
+        resource_files.extend(resource.files(resources_base_path));
+    }
+    resource_files.sort_by(|a, b| a.key.cmp(&b.key));
+    for files in resource_files.windows(2) {
+        if files[0].key == files[1].key {
+            panic!(
+                "Resource path `{}` is used by `{}` and `{}`",
+                files[0].key,
+                files[0].path.display(),
+                files[1].path.display()
+            );
+        }
+    }
+
+    if let Some(xml_path) = &config.gresource_xml {
+        resource_xml::write(xml_path, &resource_files);
+    }
+
     // Generate resource bundle
     {
-        let resources = icons
-            .iter()
-            .map(|(icon, IconData { path, is_shipped })| {
-                FileData::from_file(
-                    if *is_shipped {
-                        format!("{prefix}/scalable/actions/{icon}-symbolic.svg")
-                    } else {
-                        format!("{prefix}/scalable/actions/{icon}.svg")
-                    },
-                    path,
-                    true,
-                    &PreprocessOptions::xml_stripblanks(),
-                )
-                .unwrap()
-            })
-            .collect();
+        // Read, preprocess and compress the files in parallel
+        let resources = parallel::map(&resource_files, |file| {
+            FileData::from_file(
+                file.key.clone(),
+                &file.path,
+                file.compressed,
+                &file.preprocess_options(),
+            )
+            .unwrap()
+        });
 
         let data = BundleBuilder::from_file_data(resources)
             .build()
             .expect("Failed to build resource bundle");
 
-        fs::write(out_dir.join(&gresource_file_name), data).unwrap();
+        fs::write(out_dir.join(&gresource_file_name), &data).unwrap();
+
+        let sizes = parallel::map(&icons.iter().collect::<Vec<_>>(), |(icon, data)| {
+            let files = data.resource_files(icon);
+            report::IconSize::measure(icon, files.into_iter().map(|(_, path)| path))
+        });
+        let report = report::SizeReport::new(sizes, data.len() as u64);
+        output::info(&report);
+        report.write_json(&out_dir.join(format!("{out_file_name}.sizes.json")));
+
+        if let Some(budget) = config.size_budget
+            && report.gresource() > budget
+        {
+            panic!(
+                "Icon bundle has {} bytes, exceeding the budget of {budget} bytes. Largest icons: {}",
+                report.gresource(),
+                report.largest(5).collect::<Vec<_>>().join(", ")
+            );
+        }
     }
 
     // Create file that contains the icon names as constants
     {
         let mut out_file = BufWriter::new(File::create(out_dir.join(out_file_name)).unwrap());
 
+        let mut shipped = Module::default();
+        let mut custom = Module::default();
+        let mut semantic = Module::default();
+        let mut derived = Module::default();
+        let add_icon = |module: &mut Module, name: &str, icon: &str, doc: String, key: &str| {
+            let data = &icons[key];
+            module.add_constant(name, icon, Some(doc));
+            module.add_resource_constants(
+                name,
+                icon,
+                &format!("{prefix}/scalable/actions/{}", data.file_name(key, None)),
+                config.include_bytes.then_some(data.path.as_path()),
+            );
+        };
+
+        for (key, data) in &icons {
+            let path = &data.path;
+            if let Some(derivation) = &data.derivation {
+                let doc = format!(
+                    "Icon name of the icon `{key}`, {derivation}\n\n{}",
+                    codegen::preview(key, path)
+                );
+                add_icon(
+                    &mut derived,
+                    key.trim_end_matches("-symbolic"),
+                    key,
+                    doc,
+                    key,
+                );
+                continue;
+            }
+            if !data.is_shipped {
+                let relative_path = path.strip_prefix(icons_folder.unwrap()).unwrap();
+                let mut module = &mut custom;
+                if let Some(parent) = relative_path.parent() {
+                    for folder in parent.iter() {
+                        module = module.module(folder.to_str().unwrap());
+                    }
+                }
+                let file_name = relative_path.file_name().unwrap().to_str().unwrap();
+                let doc = format!(
+                    "Icon name of the custom icon `{key}`\n\n{}",
+                    codegen::preview(key, path)
+                );
+                add_icon(module, file_name.trim_end_matches(".svg"), key, doc, key);
+            }
+
+            let (icon, doc) = match (&data.overrides, &data.source) {
+                (Some(shipped_icon), _) => (
+                    shipped_icon,
+                    format!(
+                        "Icon name of the icon `{shipped_icon}`, overridden by a custom icon\n\n{}",
+                        codegen::preview(shipped_icon, path)
+                    ),
+                ),
+                (None, Some(source)) => (
+                    key,
+                    format!(
+                        "Icon name of the icon `{key}` from `{}` ({})\n\n{}",
+                        source.name,
+                        source.license,
+                        codegen::preview(key, path)
+                    ),
+                ),
+                (None, None) => continue,
+            };
+            add_icon(&mut shipped, icon, icon, doc, key);
+        }
+
+        for (old, new) in &renamed_icons {
+            shipped.add_deprecated_constant(old, new);
+        }
+
+        for (name, (set, icon)) in &semantic_icons {
+            let (key, data) = icons
+                .get_key_value(*icon)
+                .filter(|(_, data)| data.is_shipped)
+                .or_else(|| {
+                    icons
+                        .iter()
+                        .find(|(_, data)| data.overrides.as_deref() == Some(*icon))
+                })
+                .unwrap();
+            let origin = if data.is_shipped {
+                format!("from `{set}` ({})", set.license())
+            } else {
+                "overridden by a custom icon".into()
+            };
+            let doc = format!(
+                "Semantic icon `{name}`, resolved to `{icon}` {origin}\n\n{}",
+                codegen::preview(icon, &data.path)
+            );
+            add_icon(&mut semantic, name, icon, doc, key);
+        }
+
         writeln!(out_file, "#[rustfmt::skip]").unwrap();
         writeln!(
             out_file,
@@ -178,21 +1055,25 @@ pub fn bundle_icons<P, I, S>(
             //! module contains shipped icons\n"
         )
         .unwrap();
-        for (icon, IconData { path, is_shipped }) in &icons {
-            if *is_shipped {
-                let mut const_name = icon.to_uppercase().replace('-', "_");
-                if const_name.starts_with(|c: char| c.is_ascii_digit()) {
-                    const_name = format!("ICON_{const_name}");
-                }
-                let path = path.display();
-                writeln!(
-                    out_file,
-                    "/// Icon name of the icon `{icon}`, found at `{path}`\n\
-                    pub const {const_name}: &str = \"{icon}\";"
-                )
-                .unwrap();
-            }
-        }
+        shipped.write(&mut out_file).unwrap();
+        writeln!(out_file, "}}\n").unwrap();
+
+        writeln!(
+            out_file,
+            "pub mod semantic {{\n\
+            //! module contains semantic icons resolved against the preferred icon set\n"
+        )
+        .unwrap();
+        semantic.write(&mut out_file).unwrap();
+        writeln!(out_file, "}}\n").unwrap();
+
+        writeln!(
+            out_file,
+            "pub mod derived {{\n\
+            //! module contains icons generated from other icons at build time\n"
+        )
+        .unwrap();
+        derived.write(&mut out_file).unwrap();
         writeln!(out_file, "}}\n").unwrap();
 
         writeln!(
@@ -201,53 +1082,40 @@ pub fn bundle_icons<P, I, S>(
             //! module contains user's custom icons\n"
         )
         .unwrap();
-        let mut modules = BTreeMap::<Vec<&str>, Vec<(String, String)>>::new();
-        for (icon, IconData { path, is_shipped }) in &icons {
-            if !*is_shipped {
-                let mut path_vec = path
-                    .strip_prefix(icons_folder.as_ref().unwrap())
-                    .unwrap()
-                    .to_str()
-                    .unwrap()
-                    .split(&['/', '\\'])
-                    .collect::<Vec<_>>();
-
-                let file_name = path_vec.pop().unwrap().trim_end_matches(".svg");
-                let dir_components = path_vec;
-
-                let const_name = file_name.to_uppercase().replace('-', "_");
-                modules
-                    .entry(dir_components)
-                    .or_default()
-                    .push((const_name, icon.to_string()));
-            }
-        }
-        for (module_path, constants) in &modules {
-            if module_path.is_empty() {
-                for (const_name, const_value) in constants {
-                    writeln!(
-                        out_file,
-                        "pub const {const_name}: &str = \"{const_value}\";"
-                    )
-                    .unwrap();
-                }
-            } else {
-                for part in module_path.iter() {
-                    writeln!(out_file, "pub mod {} {{", part.replace('-', "_")).unwrap();
-                }
-                for (const_name, const_value) in constants {
-                    writeln!(
-                        out_file,
-                        "pub const {const_name}: &str = \"{const_value}\";"
-                    )
-                    .unwrap();
-                }
-                for _ in 0..module_path.len() {
-                    writeln!(out_file, "}}").unwrap();
+        custom.write(&mut out_file).unwrap();
+        writeln!(out_file, "}}").unwrap();
+
+        // Describe the bundled icons for other tools
+        let mut constants = Vec::new();
+        shipped.name_constants("shipped", &mut constants);
+        semantic.name_constants("semantic", &mut constants);
+        derived.name_constants("derived", &mut constants);
+        custom.name_constants("custom", &mut constants);
+        let mut entries = icons
+            .iter()
+            .map(|(key, data)| {
+                let file_name = data.file_name(key, None);
+                manifest::ManifestEntry {
+                    name: key.clone(),
+                    constants: constants
+                        .iter()
+                        .filter(|(_, icon)| icon == key || data.overrides.as_deref() == Some(*icon))
+                        .map(|(path, _)| path.clone())
+                        .collect(),
+                    resource_path: format!("{prefix}/scalable/actions/{file_name}"),
+                    source: data.source.as_ref().map(|source| source.name.clone()),
+                    license: data.source.as_ref().map(|source| source.license.clone()),
+                    symbolic: file_name.ends_with("-symbolic.svg"),
+                    hash: manifest::content_hash(&data.path),
                 }
-            }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        manifest::Manifest {
+            resource_prefix: prefix.clone(),
+            icons: entries,
         }
-        writeln!(out_file, "}}").unwrap();
+        .write_json(&out_dir.join(format!("{out_file_name}.manifest.json")));
         write!(
             out_file,
             "/// `GResource` file contents\n\
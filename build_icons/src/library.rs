@@ -0,0 +1,112 @@
+//! Icons required by library crates, bundled by the application.
+//!
+//! Libraries declare their icons with [`declare_icons`], which passes them to the build
+//! scripts of dependent crates as `DEP_*_RELM4_ICONS_REQUIRED` variables. Each library
+//! forwards the icons of its own dependencies, so the application bundles the union
+//! of all icons required by its dependency tree. The icon packs of a library are forwarded
+//! the same way, so the application can bundle pack icons it doesn't depend on directly.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::codegen::Module;
+use crate::{IconPack, IconSource, constants, icon_set, output, renames};
+
+/// Suffix of the metadata variable with the icons required by a library.
+const REQUIRED_SUFFIX: &str = "_RELM4_ICONS_REQUIRED";
+
+/// Icons required by the direct dependencies of the current crate and their dependencies.
+pub(crate) fn required_icons() -> BTreeSet<String> {
+    env::vars()
+        .filter(|(key, _)| key.starts_with("DEP_") && key.ends_with(REQUIRED_SUFFIX))
+        .flat_map(|(_, icons)| {
+            icons
+                .split(',')
+                .filter(|icon| !icon.is_empty())
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Declares the shipped icons a library crate needs and generates constants for their names.
+///
+/// Call this in the build script of a library instead of bundling icons. The library must set
+/// `links` in its `Cargo.toml`. Applications depending on it bundle the icons automatically,
+/// and the generated constants in the `shipped` module of `OUT_DIR/{out_file_name}` refer to
+/// the bundled icons.
+/// The declared names are also written for the `icon!` macro of `relm4-icons`.
+///
+/// # Panics
+///
+/// Panics if an icon isn't a shipped icon or part of an icon pack, or the crate doesn't set `links`.
+pub fn declare_icons<I, S>(out_file_name: &str, icon_names: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let shipped_icons = icon_set::shipped_icon_names();
+    let icon_packs = IconPack::discover();
+
+    let mut module = Module::default();
+    let mut required = required_icons();
+    // Icon names that can be used with GTK, checked by the `icon!` macro
+    let mut declared_names = BTreeSet::new();
+    for icon in icon_names {
+        let icon = icon.as_ref();
+        let exists = shipped_icons.contains(icon)
+            || icon_packs.iter().any(|pack| pack.icon_path(icon).is_some());
+        // The application bundles renamed icons under their new name
+        let name = match renames::find(icon) {
+            Some(rename) if !exists => {
+                module.add_deprecated_constant(icon, rename.new);
+                rename.new
+            }
+            _ if exists => {
+                module.add_constant(
+                    icon,
+                    icon,
+                    Some(format!(
+                        "Icon name of the icon `{icon}`, bundled by the application"
+                    )),
+                );
+                icon
+            }
+            _ => panic!("Icon with name `{icon}` does not exist"),
+        };
+        declared_names.insert(name.to_owned());
+        declared_names.insert(format!("{name}-symbolic"));
+        required.insert(icon.to_owned());
+    }
+
+    let required = required.into_iter().collect::<Vec<_>>();
+    output::metadata("relm4_icons_required", required.join(","));
+    IconPack::forward(&icon_packs);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut out_file =
+        BufWriter::new(File::create(Path::new(&out_dir).join(out_file_name)).unwrap());
+    // Same layout as the constants of `bundle_icons`, so libraries include them the same way
+    writeln!(out_file, "#[rustfmt::skip]").unwrap();
+    writeln!(
+        out_file,
+        "pub mod shipped {{\n\
+        //! module contains shipped icons\n"
+    )
+    .unwrap();
+    module.write(&mut out_file).unwrap();
+    writeln!(out_file, "}}").unwrap();
+
+    let names_manifest = declared_names
+        .iter()
+        .map(|name| format!("{name}\n"))
+        .collect::<String>();
+    fs::write(
+        Path::new(&out_dir).join(constants::NAMES_MANIFEST),
+        names_manifest,
+    )
+    .unwrap();
+}
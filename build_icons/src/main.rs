@@ -0,0 +1,102 @@
+//! Command-line bundler for build systems other than Cargo, such as Meson or Flatpak manifests.
+
+use std::env;
+use std::panic;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use relm4_icons_build::Config;
+
+const USAGE: &str = "\
+Usage: relm4-icons-build [OPTIONS] [ICON]...
+
+Bundles icons into a `.gresource` file and generates Rust constants for their names.
+
+Arguments:
+  [ICON]...                        Names of shipped icons to include
+
+Options:
+  -c, --config <FILE>              Read the configuration from a TOML file
+      --app-id <ID>                App ID, used to derive the resource path
+      --base-resource-path <PATH>  Custom base resource path
+      --icons-folder <DIR>         Directory with custom icons
+  -o, --out-dir <DIR>              Directory for the generated files [default: .]
+      --out-file <NAME>            Name of the generated Rust file [default: icon_names.rs]
+      --gresource-xml <FILE>       Also write a `.gresource.xml` manifest
+  -h, --help                       Print this help";
+
+/// Parsed command-line arguments.
+struct Args {
+    config: Config,
+    out_dir: PathBuf,
+    out_file: String,
+}
+
+/// Parses the command-line arguments, returning an error message for invalid arguments.
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut args = env::args().skip(1);
+    let mut config_file = None;
+    let mut overrides = Config::default();
+    let mut out_dir = PathBuf::from(".");
+    let mut out_file = String::from("icon_names.rs");
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{arg}`"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-c" | "--config" => config_file = Some(PathBuf::from(value()?)),
+            "--app-id" => overrides.app_id = Some(value()?),
+            "--base-resource-path" => overrides.base_resource_path = Some(value()?),
+            "--icons-folder" => overrides.icons_folder = Some(value()?.into()),
+            "-o" | "--out-dir" => out_dir = value()?.into(),
+            "--out-file" => out_file = value()?,
+            "--gresource-xml" => overrides.gresource_xml = Some(value()?.into()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ => overrides.icons.push(arg),
+        }
+    }
+
+    let mut config = config_file.map(Config::from_file).unwrap_or_default();
+    config.app_id = overrides.app_id.or(config.app_id);
+    config.base_resource_path = overrides.base_resource_path.or(config.base_resource_path);
+    config.icons_folder = overrides.icons_folder.or(config.icons_folder);
+    config.gresource_xml = overrides.gresource_xml.or(config.gresource_xml);
+    config.icons.extend(overrides.icons);
+
+    Ok(Some(Args {
+        config,
+        out_dir,
+        out_file,
+    }))
+}
+
+fn main() -> ExitCode {
+    // Bundling reports errors by panicking, which is only meant for build scripts
+    panic::set_hook(Box::new(|info| {
+        let message = info
+            .payload()
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or("bundling failed");
+        eprintln!("error: {message}");
+    }));
+
+    match parse_args() {
+        Ok(Some(args)) => {
+            relm4_icons_build::bundle_icons_to_dir(&args.out_dir, &args.out_file, &args.config);
+            ExitCode::SUCCESS
+        }
+        Ok(None) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
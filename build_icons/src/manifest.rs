@@ -0,0 +1,52 @@
+//! Machine-readable manifest of the bundled icons, for editors and other tools.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Entry of a bundled icon in the manifest.
+#[derive(Debug, Serialize)]
+pub(crate) struct ManifestEntry {
+    /// icon name as used with GTK
+    pub(crate) name: String,
+    /// paths of the generated constants, relative to the generated file
+    pub(crate) constants: Vec<String>,
+    /// full resource path of the icon
+    pub(crate) resource_path: String,
+    /// shipped set or icon pack, `None` for custom icons
+    pub(crate) source: Option<String>,
+    /// SPDX identifier of the license, `None` for custom icons
+    pub(crate) license: Option<String>,
+    /// whether GTK recolors the icon
+    pub(crate) symbolic: bool,
+    /// SHA-256 hash of the SVG file, as `sha256:{hex}`
+    pub(crate) hash: String,
+}
+
+/// Hash of the file at `path`, as `sha256:{hex}`.
+pub(crate) fn content_hash(path: &Path) -> String {
+    let digest = Sha256::digest(fs::read(path).unwrap());
+    let hex = digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256:{hex}")
+}
+
+/// Manifest of all bundled icons.
+#[derive(Debug, Serialize)]
+pub(crate) struct Manifest {
+    /// resource prefix of the icons
+    pub(crate) resource_prefix: String,
+    /// bundled icons, sorted by name
+    pub(crate) icons: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Writes the manifest as JSON.
+    pub(crate) fn write_json(&self, path: &Path) {
+        fs::write(path, serde_json::to_string_pretty(self).unwrap()).unwrap();
+    }
+}
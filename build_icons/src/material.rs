@@ -0,0 +1,114 @@
+//! Styles and variable axes of Material Symbols.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{IconPack, IconSet, index};
+
+/// Style of Material Symbols.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MaterialStyle {
+    /// Outlined style, the default.
+    #[default]
+    Outlined,
+    /// Rounded style.
+    Rounded,
+    /// Sharp style.
+    Sharp,
+}
+
+impl MaterialStyle {
+    /// Name of the style as used in the directory layout.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Outlined => "outlined",
+            Self::Rounded => "rounded",
+            Self::Sharp => "sharp",
+        }
+    }
+}
+
+/// Style, weight and fill of Material Symbols.
+///
+/// The default is the outlined style with a weight of 400 and no fill,
+/// which is the version stored directly in the `material-symbols` folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct MaterialOptions {
+    /// Style of the icon.
+    pub style: MaterialStyle,
+    /// Weight of the icon, from 100 to 700 in steps of 100.
+    pub weight: u16,
+    /// Whether to use the filled variant.
+    pub fill: bool,
+}
+
+impl Default for MaterialOptions {
+    fn default() -> Self {
+        Self {
+            style: MaterialStyle::Outlined,
+            weight: 400,
+            fill: false,
+        }
+    }
+}
+
+impl MaterialOptions {
+    /// Path of the Material Symbols icon `icon` with these options, if it exists.
+    ///
+    /// Only the default style and weight is shipped. Other styles and weights are looked up
+    /// in subdirectories such as `rounded/300` of the icon `packs`, see [`Self::is_shipped`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is not supported.
+    #[must_use]
+    pub fn icon_path(&self, icon: &str, packs: &[IconPack]) -> Option<PathBuf> {
+        assert!(
+            (100..=700).contains(&self.weight) && self.weight.is_multiple_of(100),
+            "Material Symbols weight `{}` is not supported, use 100 to 700 in steps of 100",
+            self.weight
+        );
+
+        let file_name = if self.fill && !icon.ends_with("-fill") {
+            format!("{icon}-fill-symbolic.svg")
+        } else {
+            format!("{icon}-symbolic.svg")
+        };
+        if self.is_shipped() {
+            let icon_path = IconSet::MaterialSymbols.dir().join(file_name);
+            return index::contains(&icon_path).then_some(icon_path);
+        }
+        packs.iter().find_map(|pack| {
+            let icon_path = pack
+                .dir
+                .join(self.style.name())
+                .join(self.weight.to_string())
+                .join(&file_name);
+            icon_path.exists().then_some(icon_path)
+        })
+    }
+
+    /// Whether the style and weight are shipped with `relm4-icons-build`.
+    ///
+    /// Only the outlined style with weight 400 is shipped, with and without fill.
+    #[must_use]
+    pub fn is_shipped(&self) -> bool {
+        let default = Self::default();
+        (self.style, self.weight) == (default.style, default.weight)
+    }
+}
+
+impl fmt::Display for MaterialOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.style.name(), self.weight)?;
+        if self.fill {
+            f.write_str(" filled")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//! Messages to Cargo, or to the terminal when bundling outside of a build script.
+
+use std::env;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the icons are bundled in a Cargo build script.
+static BUILD_SCRIPT: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether the icons are bundled in a Cargo build script.
+pub(crate) fn set_build_script(build_script: bool) {
+    BUILD_SCRIPT.store(build_script, Ordering::Relaxed);
+}
+
+/// Reports a warning.
+pub(crate) fn warning(message: impl Display) {
+    if BUILD_SCRIPT.load(Ordering::Relaxed) {
+        println!("cargo:warning={message}");
+    } else {
+        eprintln!("warning: {message}");
+    }
+}
+
+/// Prints information for the user when bundling outside of a build script.
+///
+/// Cargo hides the output of build scripts, so the information is skipped there.
+pub(crate) fn info(message: impl Display) {
+    if !BUILD_SCRIPT.load(Ordering::Relaxed) {
+        println!("{message}");
+    }
+}
+
+/// Passes metadata to the build scripts of dependent crates as `DEP_{LINKS}_{KEY}` variable.
+///
+/// # Panics
+///
+/// Panics if the current crate doesn't set `links`, because Cargo would drop the metadata.
+pub(crate) fn metadata(key: &str, value: impl Display) {
+    if env::var_os("CARGO_MANIFEST_LINKS").is_none() {
+        panic!(
+            "Set `links` in the `[package]` section of Cargo.toml to pass `{key}` to dependent crates"
+        );
+    }
+    println!("cargo:{key}={value}");
+}
+
+/// Asks Cargo to run the build script again if `path` changes.
+pub(crate) fn rerun_if_changed(path: &Path) {
+    if BUILD_SCRIPT.load(Ordering::Relaxed) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
@@ -0,0 +1,88 @@
+//! Shipped icons that were renamed between releases of `relm4-icons`.
+//!
+//! Updates of the icon sets and the `-alt` suffixes for colliding names can change
+//! the name of a shipped icon. Requesting an old name keeps working: the icon
+//! is resolved to its new name, the build prints a warning and the generated
+//! constant for the old name is marked as deprecated.
+
+use std::sync::LazyLock;
+
+/// A renamed shipped icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rename {
+    /// Previous name of the icon.
+    pub old: &'static str,
+    /// Current name of the icon.
+    pub new: &'static str,
+    /// Version of `relm4-icons` that renamed the icon.
+    pub since: &'static str,
+}
+
+/// Finds the current name of an icon that was renamed, following repeated renames.
+#[must_use]
+pub fn find(old: &str) -> Option<&'static Rename> {
+    follow(&RENAMES, old)
+}
+
+fn follow<'a>(renames: &'a [Rename], old: &str) -> Option<&'a Rename> {
+    let mut rename = renames.iter().find(|rename| rename.old == old)?;
+    // A chain can't be longer than the table, which also stops on cycles
+    for _ in 0..renames.len() {
+        match renames.iter().find(|next| next.old == rename.new) {
+            Some(next) => rename = next,
+            None => break,
+        }
+    }
+    Some(rename)
+}
+
+/// All renamed icons, sorted by the old name.
+///
+/// `update_icons` adds an entry to `renames.txt` whenever an update of the icon sets
+/// moves a shipped icon to another name, for example when an `-alt` suffix moves.
+pub static RENAMES: LazyLock<Vec<Rename>> = LazyLock::new(|| {
+    include_str!("renames.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut fields = line.split_whitespace();
+            let (Some(old), Some(new), Some(since)) = (fields.next(), fields.next(), fields.next())
+            else {
+                panic!("Invalid line in the rename table: `{line}`");
+            };
+            Rename { old, new, since }
+        })
+        .collect()
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(old: &'static str, new: &'static str) -> Rename {
+        Rename {
+            old,
+            new,
+            since: "0.11.0",
+        }
+    }
+
+    #[test]
+    fn follows_chains() {
+        let renames = [rename("a", "b"), rename("b", "c")];
+        assert_eq!(follow(&renames, "a").map(|rename| rename.new), Some("c"));
+        assert_eq!(follow(&renames, "b").map(|rename| rename.new), Some("c"));
+        assert_eq!(follow(&renames, "c"), None);
+    }
+
+    #[test]
+    fn stops_on_cycles() {
+        let renames = [rename("a", "b"), rename("b", "a")];
+        assert!(follow(&renames, "a").is_some());
+    }
+
+    #[test]
+    fn parses_table() {
+        assert!(RENAMES.windows(2).all(|pair| pair[0].old < pair[1].old));
+    }
+}
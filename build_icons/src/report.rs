@@ -0,0 +1,112 @@
+//! Size report of the bundled icons.
+
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use serde::Serialize;
+
+/// Sizes of a bundled icon and its variants, in bytes.
+#[derive(Debug, Serialize)]
+pub(crate) struct IconSize {
+    /// name of the icon
+    icon: String,
+    /// number of bundled files, including variants
+    files: usize,
+    /// size of the bundled files, SVGs and generated symbolic PNGs
+    uncompressed: u64,
+    /// size of the bundled files after compression
+    compressed: u64,
+}
+
+impl IconSize {
+    /// Measures the files of `icon`.
+    ///
+    /// The compressed size is approximated with the same compression as
+    /// the resource bundle, but without stripping blanks beforehand.
+    pub(crate) fn measure<'a>(icon: &str, files: impl IntoIterator<Item = &'a Path>) -> Self {
+        let mut size = Self {
+            icon: icon.to_owned(),
+            files: 0,
+            uncompressed: 0,
+            compressed: 0,
+        };
+        for file in files {
+            let data = fs::read(file).unwrap();
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+            encoder.write_all(&data).unwrap();
+            size.files += 1;
+            size.uncompressed += data.len() as u64;
+            size.compressed += encoder.finish().unwrap().len() as u64;
+        }
+        size
+    }
+}
+
+/// Sizes of all bundled icons and the resource bundle, in bytes.
+#[derive(Debug, Serialize)]
+pub(crate) struct SizeReport {
+    /// sizes of the icons, largest first
+    icons: Vec<IconSize>,
+    /// sum of the uncompressed sizes of all icons
+    total_uncompressed: u64,
+    /// sum of the compressed sizes of all icons
+    total_compressed: u64,
+    /// size of the generated `.gresource` file
+    gresource: u64,
+}
+
+impl SizeReport {
+    pub(crate) fn new(mut icons: Vec<IconSize>, gresource: u64) -> Self {
+        icons.sort_by(|a, b| b.compressed.cmp(&a.compressed).then(a.icon.cmp(&b.icon)));
+        Self {
+            total_uncompressed: icons.iter().map(|icon| icon.uncompressed).sum(),
+            total_compressed: icons.iter().map(|icon| icon.compressed).sum(),
+            icons,
+            gresource,
+        }
+    }
+
+    /// Size of the generated `.gresource` file.
+    pub(crate) const fn gresource(&self) -> u64 {
+        self.gresource
+    }
+
+    /// Largest icons, by compressed size.
+    pub(crate) fn largest(&self, count: usize) -> impl Iterator<Item = &str> {
+        self.icons.iter().take(count).map(|icon| icon.icon.as_str())
+    }
+
+    /// Writes the report as JSON.
+    pub(crate) fn write_json(&self, path: &Path) {
+        fs::write(path, serde_json::to_string_pretty(self).unwrap()).unwrap();
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>12} {:>12} {:>6}  icon",
+            "uncompressed", "compressed", "files"
+        )?;
+        for icon in &self.icons {
+            writeln!(
+                f,
+                "{:>12} {:>12} {:>6}  {}",
+                icon.uncompressed, icon.compressed, icon.files, icon.icon
+            )?;
+        }
+        writeln!(
+            f,
+            "{:>12} {:>12} {:>6}  total",
+            self.total_uncompressed,
+            self.total_compressed,
+            self.icons.iter().map(|icon| icon.files).sum::<usize>()
+        )?;
+        write!(f, "{:>12} bytes in the resource bundle", self.gresource)
+    }
+}
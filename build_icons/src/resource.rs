@@ -0,0 +1,130 @@
+//! Additional application resources bundled together with the icons.
+
+use std::path::{Path, PathBuf};
+
+use gvdb::gresource::PreprocessOptions;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::output;
+
+/// Preprocessing applied to a resource before bundling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preprocess {
+    /// Strips whitespace from XML files, such as `.ui` files.
+    XmlStripblanks,
+    /// Strips whitespace from JSON files.
+    JsonStripblanks,
+}
+
+impl Preprocess {
+    /// Name as used in `.gresource.xml` files.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::XmlStripblanks => "xml-stripblanks",
+            Self::JsonStripblanks => "json-stripblanks",
+        }
+    }
+}
+
+/// File or directory bundled as additional application resource.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Resource {
+    /// File or directory to bundle, relative to the manifest.
+    pub path: PathBuf,
+    /// Resource path of the file, or prefix of the files in the directory.
+    ///
+    /// Paths that don't start with `/` are relative to the base resource path.
+    pub resource_path: String,
+    /// Preprocessing applied to the files.
+    pub preprocess: Vec<Preprocess>,
+    /// Whether to compress the files.
+    pub compressed: bool,
+}
+
+/// File inside the resource bundle.
+#[derive(Debug, Clone)]
+pub(crate) struct ResourceFile {
+    /// full resource path
+    pub(crate) key: String,
+    /// actual location on disk
+    pub(crate) path: PathBuf,
+    pub(crate) compressed: bool,
+    pub(crate) preprocess: Vec<Preprocess>,
+}
+
+impl ResourceFile {
+    /// Icon at `path`, bundled as `key`.
+    ///
+    /// SVG files are compressed and stripped of blanks, PNG files are bundled as they are.
+    pub(crate) fn icon(key: String, path: &Path) -> Self {
+        let is_svg = path.extension().is_some_and(|ext| ext == "svg");
+        Self {
+            key,
+            path: path.to_path_buf(),
+            compressed: is_svg,
+            preprocess: if is_svg {
+                vec![Preprocess::XmlStripblanks]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// Preprocessing options for `gvdb`.
+    pub(crate) fn preprocess_options(&self) -> PreprocessOptions {
+        let mut options = PreprocessOptions::empty();
+        options.xml_stripblanks = self.preprocess.contains(&Preprocess::XmlStripblanks);
+        options.json_stripblanks = self.preprocess.contains(&Preprocess::JsonStripblanks);
+        options
+    }
+}
+
+impl Resource {
+    /// Files of this resource, with resource paths relative to `base_resource_path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path doesn't exist.
+    pub(crate) fn files(&self, base_resource_path: &str) -> Vec<ResourceFile> {
+        output::rerun_if_changed(&self.path);
+
+        let resource_path = if self.resource_path.starts_with('/') {
+            self.resource_path.trim_end_matches('/').to_owned()
+        } else if self.resource_path.is_empty() {
+            base_resource_path.to_owned()
+        } else {
+            format!(
+                "{base_resource_path}/{}",
+                self.resource_path.trim_end_matches('/')
+            )
+        };
+        let file = |key: String, path: &Path| ResourceFile {
+            key,
+            path: path.to_path_buf(),
+            compressed: self.compressed,
+            preprocess: self.preprocess.clone(),
+        };
+
+        if self.path.is_file() {
+            return vec![file(resource_path, &self.path)];
+        }
+        if !self.path.is_dir() {
+            panic!("Resource `{}` does not exist", self.path.display());
+        }
+        WalkDir::new(&self.path)
+            .sort_by_file_name()
+            .into_iter()
+            .map(|entry| entry.expect("Couldn't open resource directory specified in config"))
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| {
+                let relative_path = entry.path().strip_prefix(&self.path).unwrap();
+                let relative_path = relative_path.to_str().unwrap().replace('\\', "/");
+                file(format!("{resource_path}/{relative_path}"), entry.path())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+//! Generation of `.gresource.xml` manifests for `glib-compile-resources`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::resource::ResourceFile;
+
+/// Escapes text for use in XML attributes and elements.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes a `.gresource.xml` manifest with the same contents as the resource bundle.
+///
+/// Files are grouped by their resource directory and referenced by their absolute path,
+/// with the file name inside the bundle as alias.
+pub(crate) fn write(xml_path: &Path, files: &[ResourceFile]) {
+    let mut dirs: BTreeMap<&str, Vec<(&str, &ResourceFile)>> = BTreeMap::new();
+    for file in files {
+        let (dir, name) = file.key.rsplit_once('/').unwrap();
+        dirs.entry(dir).or_default().push((name, file));
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gresources>\n");
+    for (dir, mut files) in dirs {
+        files.sort_by_key(|(name, _)| *name);
+        let prefix = if dir.is_empty() { "/" } else { dir };
+        writeln!(xml, "  <gresource prefix=\"{}\">", escape(prefix)).unwrap();
+        for (name, file) in files {
+            write!(xml, "    <file alias=\"{}\"", escape(name)).unwrap();
+            if file.compressed {
+                xml.push_str(" compressed=\"true\"");
+            }
+            if !file.preprocess.is_empty() {
+                let preprocess = file
+                    .preprocess
+                    .iter()
+                    .map(|preprocess| preprocess.name())
+                    .collect::<Vec<_>>();
+                write!(xml, " preprocess=\"{}\"", preprocess.join(",")).unwrap();
+            }
+            let path = file.path.canonicalize().unwrap();
+            writeln!(xml, ">{}</file>", escape(path.to_str().unwrap())).unwrap();
+        }
+        xml.push_str("  </gresource>\n");
+    }
+
+    xml.push_str("</gresources>\n");
+    if let Some(dir) = xml_path.parent() {
+        fs::create_dir_all(dir).unwrap();
+    }
+    fs::write(xml_path, xml).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        assert_eq!(escape(r#"a&b<c>d"e'f"#), "a&amp;b&lt;c&gt;d&quot;e&apos;f");
+        assert_eq!(escape("edit-copy"), "edit-copy");
+    }
+
+    #[test]
+    fn escaped_manifest() {
+        let dir = env::temp_dir().join(format!("relm4-icons-xml-{}", std::process::id()));
+        let icon_dir = dir.join(r#"a&b<c"d"#);
+        fs::create_dir_all(&icon_dir).unwrap();
+        let icon_path = icon_dir.join("x&y-symbolic.svg");
+        fs::write(&icon_path, "<svg/>").unwrap();
+
+        let xml_path = dir.join("icons.gresource.xml");
+        let files = [ResourceFile::icon(
+            r#"/com/example/app&co/icons/scalable/actions/x&<"y-symbolic.svg"#.into(),
+            &icon_path,
+        )];
+        write(&xml_path, &files);
+        let xml = fs::read_to_string(&xml_path).unwrap();
+        let path = icon_path.canonicalize().unwrap();
+        let path = path.to_str().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(
+            xml.contains(r#"<gresource prefix="/com/example/app&amp;co/icons/scalable/actions">"#)
+        );
+        assert!(xml.contains(&format!(
+            r#"<file alias="x&amp;&lt;&quot;y-symbolic.svg" compressed="true" preprocess="xml-stripblanks">{}</file>"#,
+            escape(path)
+        )));
+        assert!(path.contains(r#"a&b<c"d/x&y"#));
+        assert!(!xml.contains(r#"a&b<c"d"#));
+    }
+}
@@ -0,0 +1,222 @@
+//! Detection of icons used in the Rust sources of a crate.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use walkdir::WalkDir;
+
+use crate::renames::{self, RENAMES, Rename};
+use crate::{codegen, output};
+
+/// Methods, properties and macros that take an icon name.
+const ICON_NAME_SETTERS: &[&str] = &["set_icon_name", "icon_name", "icon!"];
+
+/// Suffixes of constants generated next to the icon name constant.
+const CONSTANT_SUFFIXES: &[&str] = &["_RESOURCE_PATH", "_BYTES"];
+
+/// Known icons, shipped or from an icon pack, used in the Rust sources in `folders`.
+///
+/// Icons are detected as `icon_names::*` constant paths and as string literals
+/// passed to `set_icon_name`, the `icon_name` property or the `icon!` macro, including the
+/// `set_icon_name: "..."` and `icon_name: "..."` syntax of Relm4's `view!` macro.
+/// Names that aren't in `known_icons`, such as custom or system icons, are skipped.
+/// Deprecated constants and old names of renamed icons are detected under their old name,
+/// which the bundling resolves to the new icon.
+pub(crate) fn used_icons(
+    folders: &[impl AsRef<Path>],
+    known_icons: &BTreeSet<String>,
+) -> BTreeSet<String> {
+    let icons_by_constant = icons_by_constant(known_icons, &RENAMES);
+
+    let mut used = BTreeSet::new();
+    for folder in folders {
+        let folder = folder.as_ref();
+        output::rerun_if_changed(folder);
+
+        for entry in WalkDir::new(folder) {
+            let entry = entry.expect("Couldn't open source folder specified in config");
+            if entry.path().extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let source = fs::read_to_string(entry.path()).unwrap();
+
+            for constant in constant_paths(&source) {
+                if let Some(icon) = constant_icon(constant, &icons_by_constant) {
+                    used.insert(icon.to_owned());
+                }
+            }
+            for icon in icon_name_literals(&source) {
+                if known_icons.contains(icon) || renames::find(icon).is_some() {
+                    used.insert(icon.to_owned());
+                }
+            }
+        }
+    }
+    used
+}
+
+/// Icon names by the name of their constant, including the deprecated constants of renamed icons.
+fn icons_by_constant<'a>(
+    known_icons: &'a BTreeSet<String>,
+    renames: &'a [Rename],
+) -> HashMap<String, &'a str> {
+    let mut icons_by_constant = HashMap::new();
+    for icon in known_icons {
+        icons_by_constant
+            .entry(codegen::const_name(icon))
+            .or_insert(icon.as_str());
+    }
+    for rename in renames {
+        icons_by_constant
+            .entry(codegen::const_name(rename.old))
+            .or_insert(rename.old);
+    }
+    icons_by_constant
+}
+
+/// Icon name of a constant, which may be one of the constants generated next to the icon name.
+fn constant_icon<'a>(
+    constant: &str,
+    icons_by_constant: &HashMap<String, &'a str>,
+) -> Option<&'a str> {
+    icons_by_constant
+        .get(constant)
+        .or_else(|| {
+            CONSTANT_SUFFIXES
+                .iter()
+                .find_map(|suffix| constant.strip_suffix(suffix))
+                .and_then(|constant| icons_by_constant.get(constant))
+        })
+        .copied()
+}
+
+/// Names of constants referenced as `icon_names::NAME` or `icon_names::shipped::NAME`.
+fn constant_paths(source: &str) -> impl Iterator<Item = &str> {
+    source
+        .match_indices("icon_names::")
+        .filter_map(|(index, pattern)| {
+            let path = &source[index + pattern.len()..];
+            let path = path.strip_prefix("shipped::").unwrap_or(path);
+            let end = path
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(path.len());
+            let constant = &path[..end];
+            constant
+                .starts_with(|c: char| c.is_ascii_uppercase())
+                .then_some(constant)
+        })
+}
+
+/// String literals passed to the methods and properties in [`ICON_NAME_SETTERS`].
+fn icon_name_literals(source: &str) -> impl Iterator<Item = &str> {
+    ICON_NAME_SETTERS.iter().flat_map(move |setter| {
+        source.match_indices(setter).filter_map(move |(index, _)| {
+            let before = source[..index].chars().next_back();
+            if before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return None;
+            }
+            let rest = source[index + setter.len()..].trim_start();
+            let rest = rest.strip_prefix(['(', '[', '{', ':'])?.trim_start();
+            let rest = rest.strip_prefix("Some(").unwrap_or(rest).trim_start();
+            string_literal(rest)
+        })
+    })
+}
+
+/// Value of the string or raw string literal at the start of `source`.
+///
+/// Like the `icon!` macro, literals with escape sequences are skipped.
+fn string_literal(source: &str) -> Option<&str> {
+    if let Some(raw) = source.strip_prefix('r') {
+        let hashes = &raw[..raw.len() - raw.trim_start_matches('#').len()];
+        let literal = raw[hashes.len()..].strip_prefix('"')?;
+        let end = literal.find(&format!("\"{hashes}"))?;
+        return Some(&literal[..end]);
+    }
+    let literal = source.strip_prefix('"')?;
+    let value = &literal[..literal.find('"')?];
+    (!value.contains('\\')).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        let source = "
+            use icon_names::shipped::EDIT_COPY;
+            button.set_icon_name(icon_names::PLUS);
+            let bytes = icon_names::shipped::MINUS_BYTES;
+            include!(concat!(env!(\"OUT_DIR\"), \"/icon_names.rs\"));
+            let module = icon_names::custom::MY_ICON;
+            let lower = icon_names::lower_case;
+        ";
+        assert_eq!(
+            constant_paths(source).collect::<Vec<_>>(),
+            ["EDIT_COPY", "PLUS", "MINUS_BYTES"]
+        );
+    }
+
+    #[test]
+    fn deprecated_constants() {
+        let known_icons = BTreeSet::from(["copy-alt".to_owned(), "plus".to_owned()]);
+        let renames = [Rename {
+            old: "copy",
+            new: "copy-alt",
+            since: "0.11.0",
+        }];
+        let icons_by_constant = icons_by_constant(&known_icons, &renames);
+        assert_eq!(constant_icon("PLUS", &icons_by_constant), Some("plus"));
+        assert_eq!(
+            constant_icon("COPY_ALT", &icons_by_constant),
+            Some("copy-alt")
+        );
+        assert_eq!(constant_icon("COPY", &icons_by_constant), Some("copy"));
+        assert_eq!(
+            constant_icon("COPY_RESOURCE_PATH", &icons_by_constant),
+            Some("copy")
+        );
+        assert_eq!(constant_icon("MINUS", &icons_by_constant), None);
+    }
+
+    #[test]
+    fn literals() {
+        let source = r##"
+            button.set_icon_name("edit-copy");
+            image.set_icon_name( Some("plus") );
+            view! {
+                gtk::Button {
+                    set_icon_name: "minus",
+                },
+                gtk::Image {
+                    icon_name: "x-circle",
+                }
+            }
+            Button::builder().icon_name("check").build();
+            button.set_icon_name(name);
+            widget.reset_icon_name("not-an-icon");
+            label.set_icon_name(relm4_icons::icon!("copy"));
+            let raw = icon!(r"paste");
+            let hashed = icon!(r#"cut"#);
+            let escaped = icon!("escaped\u{2d}icon");
+            let other = my_icon!("not-an-icon");
+        "##;
+        let mut literals = icon_name_literals(source).collect::<Vec<_>>();
+        literals.sort_unstable();
+        assert_eq!(
+            literals,
+            [
+                "check",
+                "copy",
+                "cut",
+                "edit-copy",
+                "minus",
+                "paste",
+                "plus",
+                "x-circle"
+            ]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Curated mapping of common UI concepts to the equivalent icon in each shipped set.
+//!
+//! Each set names the same concept differently (`edit-delete`, `delete-regular`, `delete`).
+//! Requesting semantic icons instead of set specific names makes switching
+//! the visual style of an application a one-line change.
+
+use crate::IconSet;
+
+/// A common UI concept and its equivalent icon in each shipped set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticIcon {
+    /// Name of the concept, such as `save` or `zoom-in`.
+    pub name: &'static str,
+    /// Icon names, in the order of [`IconSet::ALL`].
+    icons: [Option<&'static str>; 4],
+}
+
+impl SemanticIcon {
+    const fn new(name: &'static str, icons: [Option<&'static str>; 4]) -> Self {
+        Self { name, icons }
+    }
+
+    /// Name of the icon representing this concept in `set`, if the set has one.
+    #[must_use]
+    pub fn icon(&self, set: IconSet) -> Option<&'static str> {
+        let index = IconSet::ALL.iter().position(|s| *s == set).unwrap();
+        self.icons[index]
+    }
+
+    /// Resolves this concept against `preferred_set`.
+    ///
+    /// Falls back to the other sets in the order of [`IconSet::ALL`]
+    /// if the preferred set has no matching icon.
+    #[must_use]
+    pub fn resolve(&self, preferred_set: IconSet) -> (IconSet, &'static str) {
+        std::iter::once(preferred_set)
+            .chain(IconSet::ALL)
+            .find_map(|set| self.icon(set).map(|icon| (set, icon)))
+            .expect("Semantic icons exist in at least one set")
+    }
+}
+
+/// Finds the semantic icon with the given concept name.
+#[must_use]
+pub fn find(name: &str) -> Option<&'static SemanticIcon> {
+    CATALOG.iter().find(|icon| icon.name == name)
+}
+
+/// All semantic icons.
+///
+/// The columns are `icon-development-kit`, `icon-development-kit-www`,
+/// `fluentui-system-icons` and `material-symbols`.
+#[rustfmt::skip]
+pub const CATALOG: &[SemanticIcon] = &[
+    SemanticIcon::new("add", [Some("list-add"), Some("plus-large"), Some("add-regular"), Some("add")]),
+    SemanticIcon::new("attach", [Some("attachment"), Some("mail-attachment"), Some("attach-regular"), Some("attach-file")]),
+    SemanticIcon::new("calendar", [Some("calendar"), None, Some("calendar-regular"), Some("calendar-today")]),
+    SemanticIcon::new("check", [None, Some("checkmark"), Some("checkmark-regular"), Some("check")]),
+    SemanticIcon::new("close", [Some("window-close"), Some("cross-large"), Some("dismiss-regular"), Some("close")]),
+    SemanticIcon::new("copy", [Some("edit-copy"), Some("copy"), Some("copy-regular"), Some("content-copy")]),
+    SemanticIcon::new("cut", [Some("scissors"), Some("cut"), Some("cut-regular"), Some("content-cut")]),
+    SemanticIcon::new("delete", [Some("edit-delete"), Some("user-trash"), Some("delete-regular"), Some("delete")]),
+    SemanticIcon::new("document", [None, Some("paper"), Some("document-regular"), Some("description")]),
+    SemanticIcon::new("download", [None, None, Some("arrow-download-regular"), Some("download")]),
+    SemanticIcon::new("edit", [Some("pencil"), Some("edit"), Some("edit-regular"), Some("edit")]),
+    SemanticIcon::new("error", [None, Some("error-outline"), Some("error-circle-regular"), Some("error")]),
+    SemanticIcon::new("favorite", [Some("heart"), Some("heart-outline-thick"), Some("heart-regular"), Some("favorite")]),
+    SemanticIcon::new("filter", [None, Some("filter"), Some("filter-regular"), Some("filter-list")]),
+    SemanticIcon::new("folder", [Some("folder"), None, Some("folder-regular"), Some("folder")]),
+    SemanticIcon::new("help", [Some("help"), Some("question-round-outline"), Some("question-circle-regular"), Some("help")]),
+    SemanticIcon::new("hide", [Some("eye-crossed"), Some("eye-closed"), Some("eye-off-regular"), Some("visibility-off")]),
+    SemanticIcon::new("home", [Some("go-home"), None, Some("home-regular"), Some("home")]),
+    SemanticIcon::new("info", [Some("info-outline"), Some("info-outline"), Some("info-regular"), Some("info")]),
+    SemanticIcon::new("link", [Some("insert-link"), Some("chain-link"), Some("link-regular"), Some("link")]),
+    SemanticIcon::new("lock", [Some("padlock-closed"), Some("padlock2"), Some("lock-closed-regular"), Some("lock")]),
+    SemanticIcon::new("mail", [Some("mail-unread"), Some("mail"), Some("mail-regular"), Some("mail")]),
+    SemanticIcon::new("menu", [Some("open-menu"), Some("menu"), Some("navigation-regular"), Some("menu")]),
+    SemanticIcon::new("more", [Some("view-more"), None, Some("more-horizontal-regular"), Some("more-horiz")]),
+    SemanticIcon::new("next", [Some("go-next"), None, Some("chevron-right-regular"), Some("chevron-right")]),
+    SemanticIcon::new("open", [Some("document-open"), Some("folder-open"), Some("open-regular"), Some("folder-open")]),
+    SemanticIcon::new("paste", [Some("edit-paste"), Some("clipboard"), Some("clipboard-paste-regular"), Some("content-paste")]),
+    SemanticIcon::new("pause", [Some("media-playback-pause"), Some("pause"), Some("pause-regular"), Some("pause")]),
+    SemanticIcon::new("play", [Some("media-playback-start"), Some("play"), Some("play-regular"), Some("play-arrow")]),
+    SemanticIcon::new("previous", [Some("go-previous"), None, Some("chevron-left-regular"), Some("chevron-left")]),
+    SemanticIcon::new("print", [Some("printer"), Some("printer"), Some("print-regular"), Some("print")]),
+    SemanticIcon::new("redo", [None, None, Some("arrow-redo-regular"), Some("redo")]),
+    SemanticIcon::new("refresh", [Some("view-refresh"), Some("arrow-circular-top-right"), Some("arrow-clockwise-regular"), Some("refresh")]),
+    SemanticIcon::new("remove", [Some("list-remove"), Some("minus-large"), Some("subtract-regular"), Some("remove")]),
+    SemanticIcon::new("save", [Some("document-save"), None, Some("save-regular"), Some("save")]),
+    SemanticIcon::new("search", [Some("loupe"), Some("loupe"), Some("search-regular"), Some("search")]),
+    SemanticIcon::new("send", [Some("mail-send"), Some("send"), Some("send-regular"), Some("send")]),
+    SemanticIcon::new("settings", [Some("cogged-wheel"), Some("settings"), Some("settings-regular"), Some("settings")]),
+    SemanticIcon::new("share", [Some("share"), Some("share"), Some("share-regular"), Some("share")]),
+    SemanticIcon::new("show", [Some("eye"), Some("eye-open"), Some("eye-regular"), Some("visibility")]),
+    SemanticIcon::new("sort", [Some("view-sort-ascending"), None, Some("arrow-sort-regular"), Some("sort")]),
+    SemanticIcon::new("star", [Some("star"), Some("star-large"), Some("star-regular"), Some("star")]),
+    SemanticIcon::new("stop", [Some("media-playback-stop"), Some("stop"), Some("stop-regular"), Some("stop")]),
+    SemanticIcon::new("undo", [None, Some("history-undo"), Some("arrow-undo-regular"), Some("undo")]),
+    SemanticIcon::new("unlock", [Some("padlock-open"), Some("padlock2-open"), Some("lock-open-regular"), Some("lock-open")]),
+    SemanticIcon::new("upload", [None, None, Some("arrow-upload-regular"), Some("upload")]),
+    SemanticIcon::new("user", [Some("person"), Some("person"), Some("person-regular"), Some("person")]),
+    SemanticIcon::new("warning", [Some("dialog-warning"), Some("warning-outline"), Some("warning-regular"), Some("warning")]),
+    SemanticIcon::new("zoom-in", [Some("loupe-plus"), Some("loupe-plus"), Some("zoom-in-regular"), Some("zoom-in")]),
+    SemanticIcon::new("zoom-out", [Some("loupe-minus"), Some("loupe-minus-large"), Some("zoom-out-regular"), Some("zoom-out")]),
+];
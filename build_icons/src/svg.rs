@@ -0,0 +1,151 @@
+//! Minimal manipulation of SVG documents.
+
+/// Byte range of the opening tag of the root `<svg>` element.
+fn root_tag(svg: &str) -> Option<(usize, usize)> {
+    let start = svg.find("<svg")?;
+    let end = start + svg[start..].find('>')?;
+    Some((start, end + 1))
+}
+
+/// Value of the attribute `name` of an XML tag.
+pub(crate) fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=");
+    tag.match_indices(&pattern).find_map(|(index, _)| {
+        if !tag[..index].ends_with(char::is_whitespace) {
+            return None;
+        }
+        let value = &tag[index + pattern.len()..];
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &value[1..];
+        value.find(quote).map(|end| &value[..end])
+    })
+}
+
+/// Parses a length such as `16` or `16px`.
+fn parse_length(length: &str) -> Option<f64> {
+    length.trim().trim_end_matches("px").parse().ok()
+}
+
+/// Canvas of the SVG document as `[min_x, min_y, width, height]`.
+pub(crate) fn view_box(svg: &str) -> Option<[f64; 4]> {
+    let (tag_start, tag_end) = root_tag(svg)?;
+    let tag = &svg[tag_start..tag_end];
+    if let Some(view_box) = attribute(tag, "viewBox") {
+        let values = view_box
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|value| !value.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<f64>, _>>()
+            .ok()?;
+        if let [min_x, min_y, width, height] = values[..] {
+            return Some([min_x, min_y, width, height]);
+        }
+    }
+    let width = attribute(tag, "width").and_then(parse_length)?;
+    // Icons are square unless stated otherwise
+    let height = attribute(tag, "height")
+        .and_then(parse_length)
+        .unwrap_or(width);
+    Some([0.0, 0.0, width, height])
+}
+
+/// Value of the attribute `name` of the root element.
+pub(crate) fn root_attribute<'a>(svg: &'a str, name: &str) -> Option<&'a str> {
+    let (tag_start, tag_end) = root_tag(svg)?;
+    attribute(&svg[tag_start..tag_end], name)
+}
+
+/// Namespace declarations with a prefix on the root element, such as ` xmlns:gpa="…"`.
+pub(crate) fn namespace_declarations(svg: &str) -> String {
+    let Some((tag_start, tag_end)) = root_tag(svg) else {
+        return String::new();
+    };
+    let tag = &svg[tag_start..tag_end];
+    tag.match_indices("xmlns:")
+        .filter_map(|(index, _)| {
+            let name = tag[index..].split('=').next()?;
+            attribute(tag, name).map(|value| format!(" {name}=\"{value}\""))
+        })
+        .collect()
+}
+
+/// Contents of the root element, without the `<svg>` tags.
+pub(crate) fn contents(svg: &str) -> Option<&str> {
+    let (_, tag_end) = root_tag(svg)?;
+    let close = svg.rfind("</svg>")?;
+    svg.get(tag_end..close)
+}
+
+/// Wraps the contents of the root element into a group with the given `transform`.
+pub(crate) fn wrap_contents(svg: &str, transform: &str) -> Option<String> {
+    let (_, tag_end) = root_tag(svg)?;
+    let close = svg.rfind("</svg>")?;
+    Some(format!(
+        "{}<g transform=\"{transform}\">{}</g>{}",
+        &svg[..tag_end],
+        contents(svg)?,
+        &svg[close..]
+    ))
+}
+
+/// Mirrors an SVG document horizontally, for example to create right-to-left variants.
+///
+/// Returns `None` if the document has no root element or no known width.
+pub(crate) fn mirror_horizontally(svg: &str) -> Option<String> {
+    let [min_x, _, width, _] = view_box(svg)?;
+    wrap_contents(svg, &format!("matrix(-1 0 0 1 {} 0)", 2.0 * min_x + width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attributes() {
+        let tag = r#"<svg width="16" data-width='24' height = "8" id='a'>"#;
+        assert_eq!(attribute(tag, "width"), Some("16"));
+        assert_eq!(attribute(tag, "id"), Some("a"));
+        assert_eq!(attribute(tag, "height"), None);
+        assert_eq!(attribute(tag, "viewBox"), None);
+    }
+
+    #[test]
+    fn view_boxes() {
+        assert_eq!(
+            view_box(r#"<?xml version="1.0"?><svg viewBox="0,-960 960 960"></svg>"#),
+            Some([0.0, -960.0, 960.0, 960.0])
+        );
+        assert_eq!(
+            view_box(r#"<svg width="16px" height="24"></svg>"#),
+            Some([0.0, 0.0, 16.0, 24.0])
+        );
+        assert_eq!(
+            view_box(r#"<svg width="16"></svg>"#),
+            Some([0.0, 0.0, 16.0, 16.0])
+        );
+        assert_eq!(view_box(r#"<svg height="16"></svg>"#), None);
+        assert_eq!(view_box("<path/>"), None);
+    }
+
+    #[test]
+    fn root_element() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:gpa='https://www.gtk.org/grappa' width="16"><path d="M0 0"/></svg>"#;
+        assert_eq!(contents(svg), Some(r#"<path d="M0 0"/>"#));
+        assert_eq!(root_attribute(svg, "width"), Some("16"));
+        assert_eq!(
+            namespace_declarations(svg),
+            r#" xmlns:gpa="https://www.gtk.org/grappa""#
+        );
+    }
+
+    #[test]
+    fn mirror() {
+        assert_eq!(
+            mirror_horizontally(r#"<svg viewBox="2 0 16 16"><path/></svg>"#).as_deref(),
+            Some(
+                r#"<svg viewBox="2 0 16 16"><g transform="matrix(-1 0 0 1 20 0)"><path/></g></svg>"#
+            )
+        );
+        assert_eq!(mirror_horizontally("<svg><path/></svg>"), None);
+    }
+}
@@ -0,0 +1,72 @@
+//! Pre-rendering of symbolic icons into GTK's symbolic PNG encoding.
+//!
+//! This matches the output of `gtk4-encode-symbolic-svg`: the icon is rendered once per
+//! semantic color, each time with that color in red and all others in green. The red
+//! channels of the three renderings become the red, green and blue channels of the PNG,
+//! which describe how much of the success, warning and error color each pixel has.
+//! The foreground color makes up the rest. GTK recolors these PNGs without an SVG loader.
+
+use resvg::{tiny_skia, usvg};
+
+/// Color of the plane that is currently extracted.
+const PLANE_COLOR: &str = "rgb(255,0,0)";
+/// Color of all other planes.
+const OTHER_COLOR: &str = "rgb(0,255,0)";
+
+/// Style sheet that recolors the icon, like the one GTK uses for symbolic SVGs.
+fn style_sheet(success: &str, warning: &str, error: &str) -> String {
+    format!(
+        "rect,circle,path {{ fill: {OTHER_COLOR} !important; }}\n\
+        .warning {{ fill: {warning} !important; }}\n\
+        .error {{ fill: {error} !important; }}\n\
+        .success {{ fill: {success} !important; }}\n"
+    )
+}
+
+/// Renders `svg` at `size` pixels, with `style_sheet` applied.
+fn render(svg: &[u8], size: u32, style_sheet: String) -> Option<tiny_skia::Pixmap> {
+    let options = usvg::Options {
+        style_sheet: Some(style_sheet),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_data(svg, &options).ok()?;
+    let mut pixmap = tiny_skia::Pixmap::new(size, size)?;
+    let transform = tiny_skia::Transform::from_scale(
+        size as f32 / tree.size().width(),
+        size as f32 / tree.size().height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Some(pixmap)
+}
+
+/// Encodes a symbolic SVG icon as symbolic PNG of `size` × `size` pixels.
+///
+/// Returns `None` if the SVG can't be parsed.
+pub(crate) fn encode(svg: &[u8], size: u32) -> Option<Vec<u8>> {
+    let planes = [
+        style_sheet(PLANE_COLOR, OTHER_COLOR, OTHER_COLOR),
+        style_sheet(OTHER_COLOR, PLANE_COLOR, OTHER_COLOR),
+        style_sheet(OTHER_COLOR, OTHER_COLOR, PLANE_COLOR),
+    ]
+    .map(|style_sheet| render(svg, size, style_sheet));
+
+    let mut rgba = vec![0; (size * size * 4) as usize];
+    for (plane, pixmap) in planes.iter().enumerate() {
+        for (pixel, color) in rgba.chunks_exact_mut(4).zip(pixmap.as_ref()?.pixels()) {
+            let color = color.demultiply();
+            pixel[plane] = color.red();
+            // The alpha channel is the same for all planes
+            pixel[3] = color.alpha();
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size, size);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&rgba))
+        .ok()?;
+    Some(png)
+}
@@ -0,0 +1,88 @@
+//! Export of the bundled icons as a freedesktop icon theme.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use walkdir::WalkDir;
+
+/// File with the description of an icon theme.
+const INDEX_THEME: &str = "index.theme";
+
+/// Copies `files` with their path relative to the theme into `theme_dir` and writes `index.theme`.
+///
+/// The paths are the same as inside the resource bundle, such as `scalable/actions/{icon}.svg`.
+pub(crate) fn export<'a>(theme_dir: &Path, files: impl IntoIterator<Item = (String, &'a Path)>) {
+    let mut dirs = BTreeSet::new();
+    for (file, path) in files {
+        let target = theme_dir.join(&file);
+        let dir = target.parent().unwrap();
+        fs::create_dir_all(dir).unwrap();
+        fs::copy(path, &target).unwrap();
+        dirs.insert(file.rsplit_once('/').unwrap().0.to_owned());
+    }
+    fs::write(theme_dir.join(INDEX_THEME), index_theme(&dirs)).unwrap();
+}
+
+/// Contents of `index.theme` for the given directories.
+fn index_theme(dirs: &BTreeSet<String>) -> String {
+    let mut index =
+        String::from("[Icon Theme]\nName=Hicolor\nComment=Fallback icon theme\nHidden=true\n");
+    writeln!(
+        index,
+        "Directories={}\n",
+        dirs.iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+    .unwrap();
+    for dir in dirs {
+        let (size, context) = dir.split_once('/').unwrap();
+        writeln!(index, "[{dir}]").unwrap();
+        writeln!(index, "Context={}", context_name(context)).unwrap();
+        match size.split_once('x') {
+            Some((size, _)) => writeln!(index, "Size={size}\nType=Fixed").unwrap(),
+            None => writeln!(index, "Size=16\nMinSize=8\nMaxSize=512\nType=Scalable").unwrap(),
+        }
+        index.push('\n');
+    }
+    index
+}
+
+/// Name of the context of a directory, such as `Actions` for `actions`.
+fn context_name(context: &str) -> String {
+    let mut chars = context.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Installs an icon theme exported with [`Config::icon_theme_dir`](crate::Config::icon_theme_dir)
+/// into `destination`, such as `/usr/share/icons/hicolor`.
+///
+/// Existing icons are replaced, an existing `index.theme` is kept.
+///
+/// # Errors
+///
+/// Returns an error if a file can't be copied.
+pub fn install_icon_theme(
+    theme_dir: impl AsRef<Path>,
+    destination: impl AsRef<Path>,
+) -> io::Result<()> {
+    let theme_dir = theme_dir.as_ref();
+    let destination = destination.as_ref();
+    for entry in WalkDir::new(theme_dir) {
+        let entry = entry?;
+        let target = destination.join(entry.path().strip_prefix(theme_dir).unwrap());
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_name() != INDEX_THEME || !target.exists() {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,190 @@
+//! Transforms that derive new icons from existing ones.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::svg;
+
+/// Icon derived from another icon by mirroring, rotating, scaling or padding it,
+/// such as `rotate-left` from `rotate-right`.
+///
+/// The icon is looked up like [`Config::icons`](crate::Config::icons),
+/// custom icons take precedence. It isn't bundled itself unless requested.
+/// All transforms are applied around the center of the icon in the order of the fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct TransformedIcon {
+    /// Name of the icon to transform.
+    pub icon: String,
+    /// Whether to mirror the icon horizontally.
+    pub mirror_horizontally: bool,
+    /// Whether to mirror the icon vertically.
+    pub mirror_vertically: bool,
+    /// Clockwise rotation in degrees, a multiple of 90.
+    pub rotate: u16,
+    /// Factor the icon is scaled by, must be positive.
+    pub scale: f64,
+    /// Empty space added on each side, relative to the size of the icon, less than 0.5.
+    ///
+    /// The icon shrinks accordingly and keeps its canvas size.
+    pub padding: f64,
+}
+
+impl Default for TransformedIcon {
+    fn default() -> Self {
+        Self {
+            icon: String::new(),
+            mirror_horizontally: false,
+            mirror_vertically: false,
+            rotate: 0,
+            scale: 1.0,
+            padding: 0.0,
+        }
+    }
+}
+
+impl TransformedIcon {
+    /// Applies the transforms to the SVG document `svg`.
+    ///
+    /// Returns `None` if the document has no root element or no known size.
+    pub(crate) fn transform(&self, svg: &str) -> Option<String> {
+        let [x, y, width, height] = svg::view_box(svg)?;
+        let (center_x, center_y) = (x + width / 2.0, y + height / 2.0);
+        let scale = self.scale * (1.0 - 2.0 * self.padding);
+        let mirror = |mirrored| if mirrored { -1.0 } else { 1.0 };
+        // SVG applies the rightmost transform first
+        let transform = format!(
+            "translate({center_x} {center_y}) scale({scale}) rotate({}) scale({} {}) translate({} {})",
+            self.rotate % 360,
+            mirror(self.mirror_horizontally),
+            mirror(self.mirror_vertically),
+            0.0 - center_x,
+            0.0 - center_y,
+        );
+        svg::wrap_contents(svg, &transform)
+    }
+}
+
+/// Describes the transforms, such as `mirrored horizontally and rotated by 90°`.
+impl fmt::Display for TransformedIcon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut steps = Vec::new();
+        if self.mirror_horizontally {
+            steps.push("mirrored horizontally".to_owned());
+        }
+        if self.mirror_vertically {
+            steps.push("mirrored vertically".to_owned());
+        }
+        if !self.rotate.is_multiple_of(360) {
+            steps.push(format!("rotated by {}°", self.rotate % 360));
+        }
+        if self.scale != 1.0 {
+            steps.push(format!("scaled by {}", self.scale));
+        }
+        if self.padding != 0.0 {
+            steps.push(format!("padded by {}", self.padding));
+        }
+        match steps.split_last() {
+            None => f.write_str("unchanged"),
+            Some((last, [])) => f.write_str(last),
+            Some((last, steps)) => write!(f, "{} and {last}", steps.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<svg viewBox="0 0 16 16"><path/></svg>"#;
+
+    fn transformed(transform: impl FnOnce(&mut TransformedIcon)) -> TransformedIcon {
+        let mut transformed = TransformedIcon {
+            icon: "go-next".into(),
+            ..Default::default()
+        };
+        transform(&mut transformed);
+        transformed
+    }
+
+    fn transform_attribute(transformed: &TransformedIcon) -> String {
+        let svg = transformed.transform(SVG).unwrap();
+        let start = svg.find("transform=\"").unwrap() + "transform=\"".len();
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end].to_owned()
+    }
+
+    #[test]
+    fn transform() {
+        assert_eq!(
+            transformed(|_| {}).transform(SVG).as_deref(),
+            Some(
+                r#"<svg viewBox="0 0 16 16"><g transform="translate(8 8) scale(1) rotate(0) scale(1 1) translate(-8 -8)"><path/></g></svg>"#
+            )
+        );
+        assert_eq!(
+            transform_attribute(&transformed(|t| t.mirror_horizontally = true)),
+            "translate(8 8) scale(1) rotate(0) scale(-1 1) translate(-8 -8)"
+        );
+        assert_eq!(
+            transform_attribute(&transformed(|t| t.mirror_vertically = true)),
+            "translate(8 8) scale(1) rotate(0) scale(1 -1) translate(-8 -8)"
+        );
+        assert_eq!(
+            transform_attribute(&transformed(|t| t.rotate = 450)),
+            "translate(8 8) scale(1) rotate(90) scale(1 1) translate(-8 -8)"
+        );
+        assert_eq!(
+            transform_attribute(&transformed(|t| t.scale = 0.5)),
+            "translate(8 8) scale(0.5) rotate(0) scale(1 1) translate(-8 -8)"
+        );
+        assert_eq!(
+            transform_attribute(&transformed(|t| t.padding = 0.25)),
+            "translate(8 8) scale(0.5) rotate(0) scale(1 1) translate(-8 -8)"
+        );
+        assert_eq!(
+            transform_attribute(&transformed(|t| {
+                t.mirror_horizontally = true;
+                t.rotate = 180;
+                t.scale = 2.0;
+                t.padding = 0.25;
+            })),
+            "translate(8 8) scale(1) rotate(180) scale(-1 1) translate(-8 -8)"
+        );
+        assert_eq!(transformed(|_| {}).transform("<svg><path/></svg>"), None);
+    }
+
+    #[test]
+    fn description() {
+        assert_eq!(transformed(|_| {}).to_string(), "unchanged");
+        assert_eq!(
+            transformed(|t| t.mirror_horizontally = true).to_string(),
+            "mirrored horizontally"
+        );
+        assert_eq!(
+            transformed(|t| t.mirror_vertically = true).to_string(),
+            "mirrored vertically"
+        );
+        assert_eq!(transformed(|t| t.rotate = 360).to_string(), "unchanged");
+        assert_eq!(
+            transformed(|t| t.rotate = 270).to_string(),
+            "rotated by 270°"
+        );
+        assert_eq!(transformed(|t| t.scale = 0.5).to_string(), "scaled by 0.5");
+        assert_eq!(
+            transformed(|t| t.padding = 0.125).to_string(),
+            "padded by 0.125"
+        );
+        assert_eq!(
+            transformed(|t| {
+                t.mirror_horizontally = true;
+                t.rotate = 90;
+                t.scale = 2.0;
+                t.padding = 0.1;
+            })
+            .to_string(),
+            "mirrored horizontally, rotated by 90°, scaled by 2 and padded by 0.1"
+        );
+    }
+}
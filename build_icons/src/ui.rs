@@ -0,0 +1,141 @@
+//! Detection of icons used in GtkBuilder `.ui` and Blueprint `.blp` files.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::{output, svg};
+
+/// Names of the icon name property.
+const ICON_NAME_PROPERTIES: &[&str] = &["icon-name", "icon_name"];
+
+/// Icon name referenced in a UI file.
+pub(crate) struct IconReference {
+    /// name of the icon
+    pub(crate) icon: String,
+    /// file that references the icon
+    file: PathBuf,
+    /// line of the reference, starting at 1
+    line: usize,
+}
+
+impl fmt::Display for IconReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.line)
+    }
+}
+
+/// Icon names referenced in the `.ui` and `.blp` files in `folders`.
+pub(crate) fn icon_references(folders: &[impl AsRef<Path>]) -> Vec<IconReference> {
+    let mut references = Vec::new();
+    for folder in folders {
+        let folder = folder.as_ref();
+        output::rerun_if_changed(folder);
+
+        for entry in WalkDir::new(folder) {
+            let entry = entry.expect("Couldn't open UI folder specified in config");
+            let icons = match entry.path().extension().and_then(|ext| ext.to_str()) {
+                Some("ui") => builder_icons,
+                Some("blp") => blueprint_icons,
+                _ => continue,
+            };
+            let source = fs::read_to_string(entry.path()).unwrap();
+            for (index, icon) in icons(&source) {
+                references.push(IconReference {
+                    icon: icon.trim().to_owned(),
+                    file: entry.path().to_path_buf(),
+                    line: source[..index].matches('\n').count() + 1,
+                });
+            }
+        }
+    }
+    references
+}
+
+/// Icon names set as `<property name="icon-name">...</property>` in a GtkBuilder file.
+fn builder_icons(source: &str) -> Vec<(usize, &str)> {
+    source
+        .match_indices("<property")
+        .filter_map(|(index, _)| {
+            let tag_end = index + source[index..].find('>')?;
+            let name = svg::attribute(&source[index..tag_end], "name")?;
+            if !ICON_NAME_PROPERTIES.contains(&name) || source[..tag_end].ends_with('/') {
+                return None;
+            }
+            let value = &source[tag_end + 1..];
+            let end = value.find("</property>")?;
+            Some((tag_end + 1, &value[..end]))
+        })
+        .collect()
+}
+
+/// Icon names set as `icon-name: "...";` in a Blueprint file.
+fn blueprint_icons(source: &str) -> Vec<(usize, &str)> {
+    ICON_NAME_PROPERTIES
+        .iter()
+        .flat_map(|property| source.match_indices(property))
+        .filter_map(|(index, property)| {
+            let before = source[..index].chars().next_back();
+            if before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                return None;
+            }
+            let rest = source[index + property.len()..].trim_start();
+            let rest = rest.strip_prefix(':')?.trim_start();
+            let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let literal = &rest[1..];
+            let start = source.len() - literal.len();
+            literal.find(quote).map(|end| (start, &literal[..end]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let source = r#"<interface>
+  <object class="GtkButton">
+    <property name="icon-name">edit-copy</property>
+  </object>
+  <object class="GtkImage">
+    <property name="icon_name" translatable="no">plus-symbolic</property>
+    <property name="label">icon-name</property>
+    <property name="icon-name"/>
+  </object>
+</interface>
+"#;
+        let icons = builder_icons(source);
+        assert_eq!(
+            icons.iter().map(|(_, icon)| *icon).collect::<Vec<_>>(),
+            ["edit-copy", "plus-symbolic"]
+        );
+        assert!(source[icons[0].0..].starts_with("edit-copy"));
+    }
+
+    #[test]
+    fn blueprint() {
+        let source = r#"using Gtk 4.0;
+
+Button {
+  icon-name: "edit-copy";
+}
+
+Image {
+  icon_name: 'plus-symbolic';
+  tooltip-text: "icon-name: \"not-an-icon\"";
+  gicon-name: "not-an-icon";
+}
+"#;
+        let icons = blueprint_icons(source);
+        let mut names = icons.iter().map(|(_, icon)| *icon).collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, ["edit-copy", "plus-symbolic"]);
+        for (index, icon) in icons {
+            assert!(source[index..].starts_with(icon));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Procedural macros for `relm4-icons`.
+//!
+//! Use them through the re-exports in `relm4-icons`.
+
+#![warn(
+    missing_debug_implementations,
+    missing_docs,
+    rust_2018_idioms,
+    unreachable_pub,
+    unused_qualifications,
+    clippy::must_use_candidate
+)]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// File in `OUT_DIR` with the names of all bundled icons, written by `relm4-icons-build`.
+const NAMES_MANIFEST: &str = "relm4-icons-names.txt";
+
+/// Maximum number of suggested icon names.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Checks at compile time that an icon is bundled and expands to its name.
+///
+/// The name is checked against the icons bundled by `relm4-icons-build`
+/// in the build script of the current crate.
+///
+/// ```ignore
+/// button.set_icon_name(relm4_icons::icon!("edit-copy"));
+/// ```
+#[proc_macro]
+pub fn icon(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let literal = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal,
+        (Some(token), _) => {
+            return compile_error("expected an icon name as string literal", token.span());
+        }
+        (None, _) => {
+            return compile_error("expected an icon name as string literal", Span::call_site());
+        }
+    };
+    let span = literal.span();
+    let icon = match string_value(&literal) {
+        Ok(icon) => icon,
+        Err(message) => return compile_error(message, span),
+    };
+
+    let Some(names) = bundled_icon_names() else {
+        return compile_error(
+            "no bundled icons found, bundle icons with `relm4-icons-build` in the build script of this crate",
+            span,
+        );
+    };
+    if names.contains(&icon) {
+        return TokenTree::Literal(literal).into();
+    }
+
+    let suggestions = suggestions(&icon, &names);
+    let message = if suggestions.is_empty() {
+        format!("icon `{icon}` is not bundled, add it to the icons in the build script")
+    } else {
+        format!(
+            "icon `{icon}` is not bundled, did you mean {}?",
+            suggestions
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+    compile_error(&message, span)
+}
+
+/// Value of a string or raw string literal without escape sequences.
+fn string_value(literal: &Literal) -> Result<String, &'static str> {
+    const NOT_A_STRING: &str = "expected an icon name as string literal";
+
+    let literal = literal.to_string();
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = &raw[..raw.len() - raw.trim_start_matches('#').len()];
+        return raw[hashes.len()..]
+            .strip_suffix(hashes)
+            .and_then(|raw| raw.strip_prefix('"')?.strip_suffix('"'))
+            .map(ToOwned::to_owned)
+            .ok_or(NOT_A_STRING);
+    }
+    let value = literal
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+        .ok_or(NOT_A_STRING)?;
+    if value.contains('\\') {
+        return Err("escape sequences aren't supported in icon names, write the name without them");
+    }
+    Ok(value.to_owned())
+}
+
+/// Names of the icons bundled for the current crate.
+fn bundled_icon_names() -> Option<Vec<String>> {
+    let out_dir = env::var("OUT_DIR").ok()?;
+    let names = fs::read_to_string(Path::new(&out_dir).join(NAMES_MANIFEST)).ok()?;
+    Some(names.lines().map(ToOwned::to_owned).collect())
+}
+
+/// Bundled icon names similar to `icon`, most similar first.
+fn suggestions<'a>(icon: &str, names: &'a [String]) -> Vec<&'a str> {
+    let max_distance = (icon.chars().count() / 3).max(2);
+    let mut candidates = names
+        .iter()
+        .map(|name| (edit_distance(icon, name), name.as_str()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Expands to `::core::compile_error!(message)` at `span`.
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut args = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    args.set_span(span);
+    let path_separator = || {
+        [
+            Punct::new(':', Spacing::Joint),
+            Punct::new(':', Spacing::Alone),
+        ]
+    };
+
+    let mut tokens = Vec::new();
+    tokens.extend(path_separator().map(TokenTree::Punct));
+    tokens.push(TokenTree::Ident(Ident::new("core", span)));
+    tokens.extend(path_separator().map(TokenTree::Punct));
+    tokens.push(TokenTree::Ident(Ident::new("compile_error", span)));
+    tokens.push(TokenTree::Punct(Punct::new('!', Spacing::Alone)));
+    tokens.push(TokenTree::Group(args));
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
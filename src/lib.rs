@@ -25,6 +25,8 @@ use gtk::{
     glib,
 };
 
+pub use relm4_icons_macros::icon;
+
 /// Initialized the icons and registers them globally for your application.
 pub fn initialize_icons(gresource_bytes: &'static [u8], resource_prefix: &str) {
     let bytes = glib::Bytes::from_static(gresource_bytes);
@@ -37,3 +39,61 @@ pub fn initialize_icons(gresource_bytes: &'static [u8], resource_prefix: &str) {
     let theme = gtk::IconTheme::for_display(&display);
     theme.add_resource_path(resource_prefix);
 }
+
+/// Resolves `icon_name` to the variant matching the current locale.
+///
+/// Localized variants are bundled as `{icon_name}-locale-{tag}`, such as
+/// `text-bold-regular-locale-de`. The languages are checked in the order
+/// returned by [`glib::language_names`]. If no variant is available or
+/// no display is open, `icon_name` is returned unchanged.
+#[must_use]
+pub fn localized_icon_name(icon_name: &str) -> String {
+    let Some(display) = gtk::gdk::Display::default() else {
+        return icon_name.to_owned();
+    };
+    let theme = gtk::IconTheme::for_display(&display);
+
+    glib::language_names()
+        .iter()
+        .filter_map(|language| locale_tag(language))
+        .map(|tag| format!("{icon_name}-locale-{tag}"))
+        .find(|name| theme.has_icon(name) || theme.has_icon(&format!("{name}-symbolic")))
+        .unwrap_or_else(|| icon_name.to_owned())
+}
+
+/// Converts a locale name such as `sr_RS.UTF-8@latin` into a tag such as `sr-latn`.
+fn locale_tag(language: &str) -> Option<String> {
+    let (language, modifier) = match language.split_once('@') {
+        Some((language, modifier)) => (language, Some(modifier)),
+        None => (language, None),
+    };
+    let language = language.split(['_', '.']).next()?.to_lowercase();
+    if language.is_empty() || language == "c" || language == "posix" {
+        return None;
+    }
+
+    Some(match modifier {
+        Some("latin") => format!("{language}-latn"),
+        Some("cyrillic") => format!("{language}-cyrl"),
+        _ => language,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::locale_tag;
+
+    #[test]
+    fn locale_tags() {
+        assert_eq!(locale_tag("de_DE.UTF-8").as_deref(), Some("de"));
+        assert_eq!(locale_tag("pt_BR").as_deref(), Some("pt"));
+        assert_eq!(locale_tag("EN").as_deref(), Some("en"));
+        assert_eq!(locale_tag("sr_RS.UTF-8@latin").as_deref(), Some("sr-latn"));
+        assert_eq!(locale_tag("sr_RS@cyrillic").as_deref(), Some("sr-cyrl"));
+        assert_eq!(locale_tag("ca_ES@valencia").as_deref(), Some("ca"));
+        assert_eq!(locale_tag("C"), None);
+        assert_eq!(locale_tag("C.UTF-8"), None);
+        assert_eq!(locale_tag("POSIX"), None);
+        assert_eq!(locale_tag(""), None);
+    }
+}
@@ -13,6 +13,23 @@ trait IconFilter {
     fn filter_dirs(_path: &Path) -> bool {
         true
     }
+    /// Variant of all icons inside the directory, such as `rtl` or `locale-de`.
+    fn dir_variant(_path: &Path) -> Option<String> {
+        None
+    }
+    /// Whether the icons inside the directory are only imported if no other icon has their name.
+    fn is_fallback_dir(_path: &Path) -> bool {
+        false
+    }
+}
+
+/// Inserts the variant before the `-symbolic.svg` suffix.
+///
+/// This is only the name inside `build_icons/icons`, matching the upstream `-rtl-symbolic.svg`
+/// files. `relm4-icons-build` bundles right-to-left variants as `{icon}-symbolic-rtl.svg`,
+/// the name GTK looks up.
+fn variant_icon_name(name: &str, variant: &str) -> String {
+    name.replace("-symbolic.svg", &format!("-{variant}-symbolic.svg"))
 }
 
 struct DevKitFilter;
@@ -47,12 +64,13 @@ impl IconFilter for DevKitWwwFilter {
     }
 }
 
+/// Filters Fluent icons designed for 16 pixels, other sizes keep their names.
 struct FluentFilter;
 
 impl IconFilter for FluentFilter {
     fn icon_name(path: &Path) -> Option<String> {
         let file_name = path.file_name().unwrap().to_str().unwrap();
-        if file_name.ends_with("16_filled.svg") || file_name.ends_with("16_regular.svg") {
+        if file_name.ends_with("_16_filled.svg") || file_name.ends_with("_16_regular.svg") {
             let file_name = file_name.trim_start_matches("ic_fluent_");
             let file_name = file_name.replace("16_", "");
             let file_name = file_name.replace('_', "-");
@@ -67,11 +85,24 @@ impl IconFilter for FluentFilter {
         name.replace("-symbolic.svg", "-fluent-alt-symbolic.svg")
     }
 
-    fn filter_dirs(path: &Path) -> bool {
+    fn dir_variant(path: &Path) -> Option<String> {
+        const SCRIPT_LIST: [&str; 2] = ["sr-cyrl", "sr-latn"];
         let dir_name = path.file_name().unwrap().to_str().unwrap();
-        const EXCLUDE_LIST: [&str; 4] = ["RTL", "LTR", "sr-cyrl", "sr-latn"];
         let is_lang_code = dir_name.len() == 2 && dir_name.chars().all(|c| c.is_ascii_lowercase());
-        !is_lang_code && EXCLUDE_LIST.iter().all(|name| !dir_name.contains(name))
+        if dir_name.contains("RTL") {
+            Some("rtl".into())
+        } else if is_lang_code || SCRIPT_LIST.contains(&dir_name) {
+            Some(format!("locale-{dir_name}"))
+        } else {
+            None
+        }
+    }
+
+    fn is_fallback_dir(path: &Path) -> bool {
+        // Left-to-right icons are the base icon for directional icons without a neutral version,
+        // right-to-left and localized icons are imported as variants
+        let dir_name = path.file_name().unwrap().to_str().unwrap();
+        dir_name.contains("LTR")
     }
 }
 
@@ -98,18 +129,79 @@ fn main() {
     analyze_dir::<DevKitFilter>("../source/icon-development-kit/icons", &mut list);
     copy_files("../build_icons/icons/icon-development-kit", list);
 
+    fetch_fluent_icons("../build_icons/icons/fluentui-system-icons");
+
+    fetch_material_icons("outlined", 400, "../build_icons/icons/material-symbols");
+
+    // Other styles and weights are too large to ship with `relm4-icons-build`.
+    // They are only written on request, as icon pack with subdirectories such as `rounded/300`.
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--material-styles" => {
+                let pack_dir = args
+                    .next()
+                    .expect("Missing directory after `--material-styles`");
+                for style in MATERIAL_STYLES {
+                    for weight in MATERIAL_WEIGHTS {
+                        if (style, weight) != ("outlined", 400) {
+                            fetch_material_icons(
+                                style,
+                                weight,
+                                &format!("{pack_dir}/{style}/{weight}"),
+                            );
+                        }
+                    }
+                }
+            }
+            _ => panic!("Unknown argument `{arg}`, expected `--material-styles <dir>`"),
+        }
+    }
+}
+
+const MATERIAL_STYLES: [&str; 3] = ["outlined", "rounded", "sharp"];
+const MATERIAL_WEIGHTS: [u16; 7] = [100, 200, 300, 400, 500, 600, 700];
+
+fn fetch_material_icons(style: &str, weight: u16, path: &str) {
     let mut list = HashMap::new();
-    analyze_dir::<FluentFilter>("../source/fluentui-system-icons/assets", &mut list);
-    copy_files("../build_icons/icons/fluentui-system-icons", list);
+    analyze_dir::<MaterialFilter>(
+        &format!("../source/material-symbols/svg/{weight}/{style}"),
+        &mut list,
+    );
+    copy_files(path, list);
+}
+
+/// Hand-tuned Fluent sizes that have a hicolor size directory.
+///
+/// The 20 and 28 pixel versions are skipped, GTK doesn't look them up.
+const FLUENT_SIZES: [u32; 3] = [24, 32, 48];
 
+fn fetch_fluent_icons(path: &str) {
     let mut list = HashMap::new();
-    analyze_dir::<MaterialFilter>("../source/material-symbols/svg/400/outlined", &mut list);
-    copy_files("../build_icons/icons/material-symbols", list);
+    analyze_dir::<FluentFilter>("../source/fluentui-system-icons/assets", &mut list);
+
+    // Hand-tuned versions of other sizes are bundled into the matching hicolor size directory.
+    // They keep the name chosen for the 16 pixel version from the same asset folder,
+    // so a name always refers to the same drawing at all sizes.
+    for size in FLUENT_SIZES {
+        let sized_list = list
+            .iter()
+            .filter_map(|(name, source)| {
+                let file_name = source.file_name()?.to_str()?;
+                let (stem, style) = file_name.rsplit_once("_16_")?;
+                let sized_source = source.with_file_name(format!("{stem}_{size}_{style}"));
+                sized_source.exists().then(|| (name.clone(), sized_source))
+            })
+            .collect();
+        copy_files(Path::new(path).join(format!("{size}x{size}")), sized_list);
+    }
+
+    copy_files(path, list);
 }
 
-fn copy_files(path: &str, list: HashMap<String, PathBuf>) {
-    let path = Path::new(path);
-    std::fs::create_dir(path).ok();
+fn copy_files(path: impl AsRef<Path>, list: HashMap<String, PathBuf>) {
+    let path = path.as_ref();
+    std::fs::create_dir_all(path).ok();
     for (key, value) in list.into_iter() {
         std::fs::copy(value, path.join(key)).unwrap();
     }
@@ -117,10 +209,20 @@ fn copy_files(path: &str, list: HashMap<String, PathBuf>) {
 
 fn analyze_dir<F: IconFilter>(path: &str, list: &mut HashMap<String, PathBuf>) {
     let dir = std::fs::read_dir(path).expect(path);
-    analyze_dir_recursively::<F>(dir, list);
+    let mut fallbacks = HashMap::new();
+    analyze_dir_recursively::<F>(dir, None, false, list, &mut fallbacks);
+    for (name, path) in fallbacks {
+        list.entry(name).or_insert(path);
+    }
 }
 
-fn analyze_dir_recursively<F: IconFilter>(dir: fs::ReadDir, list: &mut HashMap<String, PathBuf>) {
+fn analyze_dir_recursively<F: IconFilter>(
+    dir: fs::ReadDir,
+    variant: Option<&str>,
+    fallback: bool,
+    list: &mut HashMap<String, PathBuf>,
+    fallbacks: &mut HashMap<String, PathBuf>,
+) {
     for entry in dir {
         let entry = entry.unwrap();
         let file_type = entry.file_type().unwrap();
@@ -128,9 +230,14 @@ fn analyze_dir_recursively<F: IconFilter>(dir: fs::ReadDir, list: &mut HashMap<S
 
         if file_type.is_file() {
             if let Some(name) = F::icon_name(&path) {
-                // Try normal name
-                if list.contains_key(&name) {
-                    // Try alternative name
+                let name = match variant {
+                    Some(variant) => variant_icon_name(&name, variant),
+                    None => name,
+                };
+                if fallback {
+                    fallbacks.entry(name).or_insert(path);
+                } else if list.contains_key(&name) {
+                    // Normal name is taken, try alternative name
                     let alt_name = F::alt_icon_name(&name);
                     if list.contains_key(&alt_name) {
                         // Try 2nd alternative name
@@ -150,8 +257,13 @@ fn analyze_dir_recursively<F: IconFilter>(dir: fs::ReadDir, list: &mut HashMap<S
                 }
             }
         } else if file_type.is_dir() && F::filter_dirs(&path) {
+            let variant = match (variant, F::dir_variant(&path)) {
+                (Some(outer), Some(inner)) => Some(format!("{outer}-{inner}")),
+                (outer, inner) => inner.or(outer.map(Into::into)),
+            };
+            let fallback = fallback || F::is_fallback_dir(&path);
             let dir = std::fs::read_dir(path).unwrap();
-            analyze_dir_recursively::<F>(dir, list);
+            analyze_dir_recursively::<F>(dir, variant.as_deref(), fallback, list, fallbacks);
         }
     }
 }
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+const BASE_ICON_PATH: &str = "../build_icons/icons";
+
+/// An entry of the index: the path of an icon relative to the icon folder and a hash of its contents.
+pub struct IndexEntry<'a> {
+    pub path: &'a str,
+    pub hash: Option<&'a str>,
+}
+
+/// Parses the lines `{path} {hash}` of an index.
+pub fn parse(index: &str) -> Vec<IndexEntry<'_>> {
+    index
+        .lines()
+        .map(|line| match line.split_once(' ') {
+            Some((path, hash)) => IndexEntry {
+                path,
+                hash: Some(hash),
+            },
+            None => IndexEntry {
+                path: line,
+                hash: None,
+            },
+        })
+        .collect()
+}
+
+/// Writes the paths of all shipped icons relative to the icon folder together with a hash
+/// of their contents, one per line and sorted by path, so `relm4-icons-build` can look up icons
+/// without touching the file system and `update_icons` can detect renamed icons.
+pub fn generate(index_path: &str) {
+    let mut files = Vec::new();
+    collect_files(Path::new(BASE_ICON_PATH), &mut files);
+    files.sort_unstable();
+
+    let mut index = String::new();
+    for (path, hash) in files {
+        index.push_str(&format!("{path} {hash:016x}\n"));
+    }
+    std::fs::write(index_path, index).unwrap();
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<(String, u64)>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        if entry.file_type().unwrap().is_dir() {
+            collect_files(&path, files);
+        } else if path.to_str().unwrap().ends_with("-symbolic.svg") {
+            let relative_path = path.strip_prefix(BASE_ICON_PATH).unwrap();
+            let components = relative_path
+                .iter()
+                .map(|component| component.to_str().unwrap())
+                .collect::<Vec<_>>();
+            let hash = fnv1a(&std::fs::read(&path).unwrap());
+            files.push((components.join("/"), hash));
+        }
+    }
+}
+
+/// 64 bit FNV-1a hash, which is stable across Rust versions unlike the hasher of `std`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
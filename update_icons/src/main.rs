@@ -3,13 +3,17 @@ use std::path::PathBuf;
 use crate::code_generator::Generator;
 
 mod code_generator;
+mod icon_index;
 mod icon_list;
+mod renames;
 
 const HTML_START_SEQ: &str = "<!-- AUTOGENERATED";
 const HTML_END_SEQ: &str = "<!-- END AUTOGENERATED";
 const ICONS16: &str = "../icons16.md";
 const ICONS32: &str = "../icons32.md";
 const ICONS64: &str = "../icons64.md";
+const ICON_INDEX: &str = "../build_icons/src/shipped_index.txt";
+const RENAMES: &str = "../build_icons/src/renames.txt";
 
 pub struct IconInfo {
     pub name: String,
@@ -28,6 +32,12 @@ impl IconInfo {
 }
 
 fn main() {
+    let old_index = std::fs::read_to_string(ICON_INDEX).unwrap_or_default();
+    icon_index::generate(ICON_INDEX);
+    let new_index = std::fs::read_to_string(ICON_INDEX).unwrap();
+    let version = renames::workspace_version("../Cargo.toml");
+    renames::update(&old_index, &new_index, RENAMES, &version);
+
     let icon_list = icon_list::get_icons(&[
         "icon-development-kit-www",
         "icon-development-kit",
@@ -0,0 +1,87 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::icon_index::{self, IndexEntry};
+
+/// Adds icons whose drawing moved to another name, such as when the `-alt` suffix for
+/// colliding names moves to another icon, to the rename table of `relm4-icons-build`.
+///
+/// Only names that don't exist anymore are added, so requesting them keeps working.
+pub fn update(old_index: &str, new_index: &str, renames_path: &str, version: &str) {
+    let old_entries = icon_index::parse(old_index);
+    let new_entries = icon_index::parse(new_index);
+
+    let new_names = new_entries
+        .iter()
+        .filter_map(icon_name)
+        .map(|(_, name)| name)
+        .collect::<HashSet<_>>();
+    let old_hashes = old_entries
+        .iter()
+        .map(|entry| (entry.path, entry.hash))
+        .collect::<HashMap<_, _>>();
+    // Icons of the new index whose drawing changed, by set and hash
+    let mut moved = HashMap::<_, Vec<_>>::new();
+    for entry in &new_entries {
+        let (Some((set, name)), Some(hash)) = (icon_name(entry), entry.hash) else {
+            continue;
+        };
+        if old_hashes.get(entry.path) != Some(&Some(hash)) {
+            moved.entry((set, hash)).or_default().push(name);
+        }
+    }
+
+    let contents = std::fs::read_to_string(renames_path).unwrap();
+    let mut renames = contents
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let old = line.split_whitespace().next().unwrap().to_owned();
+            (old, line.to_owned())
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    for entry in &old_entries {
+        let (Some((set, old)), Some(hash)) = (icon_name(entry), entry.hash) else {
+            continue;
+        };
+        if new_names.contains(old) || renames.contains_key(old) {
+            continue;
+        }
+        if let Some([new]) = moved.get(&(set, hash)).map(Vec::as_slice) {
+            println!("Icon `{old}` was renamed to `{new}`");
+            renames.insert(old.to_owned(), format!("{old} {new} {version}"));
+        }
+    }
+
+    let header = contents
+        .lines()
+        .take_while(|line| line.starts_with('#'))
+        .collect::<Vec<_>>();
+    let mut output = header.join("\n");
+    output.push('\n');
+    for line in renames.values() {
+        output.push_str(line);
+        output.push('\n');
+    }
+    std::fs::write(renames_path, output).unwrap();
+}
+
+/// Set and name of an icon in the index, without hand-tuned sizes or other styles in subfolders.
+fn icon_name<'a>(entry: &IndexEntry<'a>) -> Option<(&'a str, &'a str)> {
+    let (set, file_name) = entry.path.split_once('/')?;
+    if file_name.contains('/') {
+        return None;
+    }
+    Some((set, file_name.strip_suffix("-symbolic.svg")?))
+}
+
+/// Version of `relm4-icons` in the workspace manifest.
+pub fn workspace_version(manifest_path: &str) -> String {
+    let manifest = std::fs::read_to_string(manifest_path).unwrap();
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("version = \""))
+        .and_then(|version| version.strip_suffix('"'))
+        .expect("workspace manifest without version")
+        .to_owned()
+}